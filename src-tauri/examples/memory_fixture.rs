@@ -0,0 +1,54 @@
+// 内存读取测试中被读取的进程, 由 test/process.rs 的 process_chain 启动
+// 输出根指针和不可读页边界的地址后等待标准输入
+
+#[cfg(target_os = "linux")]
+fn main() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static ROOT: AtomicUsize = AtomicUsize::new(0);
+
+    // 0x10 数值 1234, 0x14 为 1.5, 0x20 GBK "火球", 0x30 UTF-16 "冰箭"
+    let mut values = vec![0u8; 0x40];
+    values[0x10..0x14].copy_from_slice(&1234u32.to_le_bytes());
+    values[0x14..0x18].copy_from_slice(&1.5f32.to_le_bytes());
+    values[0x20..0x24].copy_from_slice(&[0xBB, 0xF0, 0xC7, 0xF2]);
+    for (i, c) in "冰箭".encode_utf16().enumerate() {
+        values[0x30 + i * 2..0x32 + i * 2].copy_from_slice(&c.to_le_bytes());
+    }
+    let values = values.leak();
+    // holder[2] 指向 values, holder[0] 为空指针
+    let holder = vec![0usize, 0, values.as_ptr() as usize, 0].leak();
+    ROOT.store(holder.as_ptr() as usize, Ordering::SeqCst);
+
+    // 两页内存的第二页不可读, 跨页读取时只能读到一部分
+    let page = 4096;
+    let edge = unsafe {
+        let pages = libc::mmap(
+            std::ptr::null_mut(),
+            page * 2,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        );
+        assert_ne!(pages, libc::MAP_FAILED);
+        libc::mprotect(
+            (pages as usize + page) as *mut libc::c_void,
+            page,
+            libc::PROT_NONE,
+        );
+        pages as usize + page - 4
+    };
+
+    // ROOT 在可执行文件的数据段中, 读取方用模块基址加偏移找到它
+    let root = &ROOT as *const AtomicUsize as usize;
+    println!("fixture: {} {}", root, edge);
+
+    let mut line = String::new();
+    let _ = std::io::stdin().read_line(&mut line);
+}
+
+#[cfg(not(target_os = "linux"))]
+fn main() {
+    eprintln!("只用于 Linux 下的内存读取测试");
+}
@@ -8,13 +8,16 @@ use std::{
 };
 
 use crate::{
-    act::send::simulate_key,
+    act::{
+        context::RunContext,
+        send::{simulate_key, simulate_mouse},
+    },
     global::{
-        global::{PROCESS_NAME, TIME_CHECK_TIME},
+        global::TIME_CHECK_TIME,
         model::{Children, ElementEnum},
     },
-    memory::read::GameMemoryReader,
-    rgb::check::check_color_distance,
+    memory::attach::target,
+    rgb::check::parse_coordinate,
 };
 
 fn collect_children(children: Option<Vec<Children>>, target_iyn: &str) -> Vec<Children> {
@@ -32,6 +35,7 @@ fn collect_children(children: Option<Vec<Children>>, target_iyn: &str) -> Vec<Ch
 pub fn run_element(
     elements: Vec<Children>,
     stop_flag: Arc<AtomicBool>,
+    ctx: &mut RunContext,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut next_level: Vec<Children> = Vec::new();
 
@@ -43,13 +47,12 @@ pub fn run_element(
         let children_to_add = match &c.element {
             ElementEnum::Element(e) => {
                 // println!("执行元素: {:?}", e);
-                simulate_key(e.elements_code.clone(), e.key_up_delay)
+                simulate_key(e.elements_code, e.key_up_delay)
                     .map_err(|e| format!("Failed to simulate key: {:?}", e))?;
                 collect_children(c.children.clone(), "y")
             }
             ElementEnum::Skill(s) => {
-                let process_name = PROCESS_NAME.clone();
-                match GameMemoryReader::new(&process_name, 0x00400000) {
+                match target().process() {
                     Ok(reader) => {
                         // s.skill_offset是Option<String>类型，需要处理成usize
                         let skill_offset = s
@@ -92,7 +95,7 @@ pub fn run_element(
                         }
                     }
                     Err(e) => {
-                        eprintln!("Failed to create GameMemoryReader: {:?}", e);
+                        eprintln!("Failed to open process: {:?}", e);
                         collect_children(c.children.clone(), "n")
                     }
                 }
@@ -110,11 +113,7 @@ pub fn run_element(
                             if let Some(last_time) = last_time_map.get(e_id) {
                                 let duration = last_time.elapsed().as_millis();
                                 // println!("元素 {} 上次执行时间: {:?} 毫秒", e_id, duration);
-                                if duration <= t.n.into() {
-                                    true
-                                } else {
-                                    false
-                                }
+                                duration <= t.n.into()
                             } else {
                                 if t.init {
                                     last_time_map.insert(e_id.clone(), Instant::now());
@@ -133,27 +132,24 @@ pub fn run_element(
                     let target_iyn = if check_result { "y" } else { "n" };
                     collect_children(c.children.clone(), target_iyn)
                 }
-                3 => {
-                    let process_name = PROCESS_NAME.clone();
-                    match GameMemoryReader::new(&process_name, 0x00400000) {
-                        Ok(reader) => {
-                            let inner_power = reader.read_mana().unwrap_or_else(|e| {
-                                eprintln!("Error reading inner power: {:?}", e);
-                                0
-                            });
-                            let target_iyn = if inner_power <= t.n.try_into().unwrap_or(0) {
-                                "y"
-                            } else {
-                                "n"
-                            };
-                            collect_children(c.children.clone(), target_iyn)
-                        }
-                        Err(e) => {
-                            eprintln!("Failed to create GameMemoryReader for mana: {:?}", e);
-                            collect_children(c.children.clone(), "n")
-                        }
+                3 => match target().process() {
+                    Ok(reader) => {
+                        let inner_power = reader.read_mana().unwrap_or_else(|e| {
+                            eprintln!("Error reading inner power: {:?}", e);
+                            0
+                        });
+                        let target_iyn = if inner_power <= t.n.try_into().unwrap_or(0) {
+                            "y"
+                        } else {
+                            "n"
+                        };
+                        collect_children(c.children.clone(), target_iyn)
                     }
-                }
+                    Err(e) => {
+                        eprintln!("Failed to open process for mana: {:?}", e);
+                        collect_children(c.children.clone(), "n")
+                    }
+                },
                 4 => {
                     let now = Instant::now();
                     match TIME_CHECK_TIME.try_lock() {
@@ -175,18 +171,44 @@ pub fn run_element(
                 _ => Vec::new(),
             },
             ElementEnum::Color(co) => {
-                let check_result = match std::panic::catch_unwind(|| {
-                    check_color_distance(co.rgb.clone(), co.coordinate.clone(), 0)
-                }) {
-                    Ok(result) => result,
-                    Err(_) => {
-                        eprintln!("Color check panicked, using false as default");
-                        false
-                    }
-                };
+                // 同一轮的取色共用一张截图
+                let check_result = ctx.check_color(co);
                 let target_iyn = if check_result { "y" } else { "n" };
                 collect_children(c.children.clone(), target_iyn)
             }
+            ElementEnum::ColorPattern(cp) => {
+                let check_result = ctx.check_pattern(cp);
+                let target_iyn = if check_result { "y" } else { "n" };
+                collect_children(c.children.clone(), target_iyn)
+            }
+            ElementEnum::Template(t) => {
+                let check_result = ctx.check_template(t);
+                let target_iyn = if check_result { "y" } else { "n" };
+                collect_children(c.children.clone(), target_iyn)
+            }
+            ElementEnum::Bar(b) => {
+                let check_result = ctx.check_bar(b);
+                let target_iyn = if check_result { "y" } else { "n" };
+                collect_children(c.children.clone(), target_iyn)
+            }
+            ElementEnum::Digits(d) => {
+                let check_result = ctx.check_digits(d);
+                let target_iyn = if check_result { "y" } else { "n" };
+                collect_children(c.children.clone(), target_iyn)
+            }
+            ElementEnum::Mouse(m) => {
+                // 没有设置坐标时使用最近找到的位置
+                let position = match &m.position {
+                    Some(position) => parse_coordinate(position),
+                    None => ctx.found,
+                };
+                match position {
+                    Some((x, y)) => simulate_mouse(x, y, &m.mouse)
+                        .map_err(|e| format!("Failed to simulate mouse: {:?}", e))?,
+                    None => eprintln!("没有可用的鼠标位置, 跳过鼠标操作"),
+                }
+                collect_children(c.children.clone(), "y")
+            }
         };
 
         // let elapsed = start_time.elapsed();
@@ -203,7 +225,7 @@ pub fn run_element(
     }
 
     if !next_level.is_empty() {
-        run_element(next_level, stop_flag)?;
+        run_element(next_level, stop_flag, ctx)?;
     }
     Ok(())
 }
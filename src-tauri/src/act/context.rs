@@ -0,0 +1,375 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    global::model::{
+        Bar, CaptureMode, Children, Color, ColorPattern, Digits, ElementEnum, Template,
+    },
+    rgb::{
+        bar::BarGauge,
+        capture::FrameCache,
+        check::{parse_coordinate, parse_rgb},
+        debug::{crop_around, queue_capture, CaptureConfig, CaptureTracker},
+        digits::{number_holds, DigitReader, GlyphSet},
+        metric::color_matches,
+        monitor::virtual_bounds,
+        pattern::{parse_region, Pattern},
+        source::{screen, ScreenSource},
+        template::TemplateImage,
+    },
+    user::user::{get_capture_config, get_captures_dir},
+};
+
+/// 流程中所有取色节点的坐标
+pub fn color_points(children: &[Children]) -> Vec<(i32, i32)> {
+    let mut points = Vec::new();
+    for c in children {
+        match &c.element {
+            ElementEnum::Color(co) => points.extend(parse_coordinate(&co.coordinate)),
+            ElementEnum::ColorPattern(cp) => {
+                if let Ok(pattern) = Pattern::parse(cp) {
+                    points.extend(pattern.corners(cp));
+                }
+            }
+            ElementEnum::Template(t) => {
+                if let Some((x, y, width, height)) = parse_region(&t.region) {
+                    points.push((x, y));
+                    points.push((x + width as i32 - 1, y + height as i32 - 1));
+                }
+            }
+            ElementEnum::Bar(b) => {
+                if let Ok(gauge) = BarGauge::parse(b) {
+                    points.extend(gauge.corners());
+                }
+            }
+            ElementEnum::Digits(d) => {
+                if let Ok(reader) = DigitReader::parse(d) {
+                    points.extend(reader.corners());
+                }
+            }
+            _ => {}
+        }
+        if let Some(children) = &c.children {
+            points.extend(color_points(children));
+        }
+    }
+    points
+}
+
+/// 流程中开启了调试截图的取色节点的坐标
+pub fn capture_points(children: &[Children]) -> Vec<(i32, i32)> {
+    let mut points = Vec::new();
+    for c in children {
+        if let ElementEnum::Color(co) = &c.element {
+            if co.capture != CaptureMode::Off {
+                points.extend(parse_coordinate(&co.coordinate));
+            }
+        }
+        if let Some(children) = &c.children {
+            points.extend(capture_points(children));
+        }
+    }
+    points
+}
+
+/// 调试截图区分节点的标识, 旧流程没有节点id时使用坐标和颜色
+pub fn capture_key(co: &Color) -> String {
+    match &co.node {
+        Some(node) => node.clone(),
+        None => format!("{}|{}", co.coordinate, co.rgb),
+    }
+}
+
+/**
+ * 一次流程执行的上下文, 在多轮执行之间保留
+ */
+pub struct RunContext {
+    pub frames: FrameCache,                            // 取色共用的截图
+    pub found: Option<(i32, i32)>,                     // 最近一次多点取色或模板匹配找到的位置
+    templates: HashMap<String, Option<TemplateImage>>, // 已读取的模板, 读取失败的为 None
+    glyphs: HashMap<String, Option<GlyphSet>>,         // 已读取的字形集, 读取失败的为 None
+    captures: CaptureTracker,                          // 调试截图的节点状态
+    capture_config: Option<CaptureConfig>,             // 有节点开启调试截图时读取
+}
+
+impl RunContext {
+    /// frame_age: 截图最长复用时间ms, 为空时每轮截图一次
+    pub fn new(children: &[Children], frame_age: Option<u64>) -> Self {
+        let mut frames = FrameCache::new(
+            &color_points(children),
+            frame_age.map(Duration::from_millis),
+        );
+        // 调试截图从本轮截图中裁剪, 截图区域扩大到开启截图的坐标周围, 不超出屏幕
+        let captures = capture_points(children);
+        let mut capture_config = None;
+        if !captures.is_empty() {
+            let config = get_capture_config().unwrap_or_default();
+            let bounds = screen()
+                .and_then(|source| source.monitors())
+                .ok()
+                .and_then(|monitors| virtual_bounds(&monitors));
+            if let Some(bounds) = bounds {
+                let side = config.max_side.max(1);
+                let r = side as i32 / 2;
+                for (x, y) in captures {
+                    frames.widen((x - r, y - r, side, side), bounds);
+                }
+            }
+            capture_config = Some(config);
+        }
+        RunContext {
+            frames,
+            found: None,
+            templates: HashMap::new(),
+            glyphs: HashMap::new(),
+            captures: CaptureTracker::default(),
+            capture_config,
+        }
+    }
+
+    /// 每轮执行开始时调用
+    pub fn begin_cycle(&mut self) {
+        self.frames.begin_cycle();
+    }
+
+    /// 校验取色节点
+    pub fn check_color(&mut self, co: &Color) -> bool {
+        let ((x, y), target_color) = match (parse_coordinate(&co.coordinate), parse_rgb(&co.rgb)) {
+            (Some(point), Some(rgb)) => (point, rgb),
+            _ => {
+                eprintln!("取色配置有误: {} {}", co.coordinate, co.rgb);
+                return false;
+            }
+        };
+        let source = match screen() {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("取色失败: {}", e);
+                return false;
+            }
+        };
+        let now = Instant::now();
+        match self.frames.pixel(source.as_ref(), x, y, now) {
+            Ok(rgb) => {
+                let matched =
+                    color_matches(co.metric, rgb, target_color, co.tolerance, co.saturation);
+                self.capture(co, source.as_ref(), (x, y), matched, now);
+                matched
+            }
+            Err(e) => {
+                eprintln!("取色失败: {}", e);
+                false
+            }
+        }
+    }
+
+    // 取色节点开启调试截图时, 在后台保存本轮的截图
+    fn capture(
+        &mut self,
+        co: &Color,
+        source: &dyn ScreenSource,
+        (x, y): (i32, i32),
+        matched: bool,
+        now: Instant,
+    ) {
+        if co.capture == CaptureMode::Off {
+            return;
+        }
+        let config = self
+            .capture_config
+            .get_or_insert_with(|| get_capture_config().unwrap_or_default())
+            .clone();
+        let key = capture_key(co);
+        let interval = Duration::from_millis(config.interval);
+        if !self
+            .captures
+            .record(&key, co.capture, matched, now, interval)
+        {
+            return;
+        }
+        // 从本轮取色用的截图中裁剪, 不再单独截图
+        let frame = match self.frames.frame(source, now) {
+            Ok(Some(frame)) if frame.pixel(x, y).is_some() => {
+                crop_around(frame, x, y, config.max_side)
+            }
+            _ => return,
+        };
+        let dir = match get_captures_dir() {
+            Some(dir) => dir,
+            None => return,
+        };
+        queue_capture(
+            dir,
+            co.coordinate.clone(),
+            frame,
+            vec![(x, y, matched)],
+            config,
+        );
+    }
+
+    /// 校验多点取色节点, 成立时记录锚点位置
+    pub fn check_pattern(&mut self, cp: &ColorPattern) -> bool {
+        let pattern = match Pattern::parse(cp) {
+            Ok(pattern) => pattern,
+            Err(e) => {
+                eprintln!("多点取色配置有误: {}", e);
+                return false;
+            }
+        };
+        let source = match screen() {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("取色失败: {}", e);
+                return false;
+            }
+        };
+        let frame = match self.frames.frame(source.as_ref(), Instant::now()) {
+            Ok(Some(frame)) => frame,
+            Ok(None) => {
+                eprintln!("多点取色缺少锚点或搜索区域");
+                return false;
+            }
+            Err(e) => {
+                eprintln!("取色失败: {}", e);
+                return false;
+            }
+        };
+        let pixel = |x, y| frame.pixel(x, y);
+        let found = match cp.search.as_deref() {
+            Some(region) => match parse_region(region) {
+                Some(region) => pattern.find(region, pixel),
+                None => {
+                    eprintln!("搜索区域有误: {}", region);
+                    None
+                }
+            },
+            None => cp
+                .anchor
+                .as_deref()
+                .and_then(parse_coordinate)
+                .filter(|(x, y)| pattern.matches_at(*x, *y, pixel)),
+        };
+        if found.is_some() {
+            self.found = found;
+        }
+        found.is_some()
+    }
+
+    /// 校验模板匹配节点, store 为 true 时记录模板中心位置
+    pub fn check_template(&mut self, t: &Template) -> bool {
+        let region = match parse_region(&t.region) {
+            Some(region) => region,
+            None => {
+                eprintln!("搜索区域有误: {}", t.region);
+                return false;
+            }
+        };
+        // 每个模板只读取一次, 读取失败也只提示一次
+        if !self.templates.contains_key(&t.template) {
+            let template = TemplateImage::load(&t.template)
+                .map_err(|e| eprintln!("{}", e))
+                .ok();
+            self.templates.insert(t.template.clone(), template);
+        }
+        let template = match self.templates.get(&t.template) {
+            Some(Some(template)) => template,
+            _ => return false,
+        };
+        let source = match screen() {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("取色失败: {}", e);
+                return false;
+            }
+        };
+        let frame = match self.frames.frame(source.as_ref(), Instant::now()) {
+            Ok(Some(frame)) => frame,
+            Ok(None) => return false,
+            Err(e) => {
+                eprintln!("截图失败: {}", e);
+                return false;
+            }
+        };
+        match template.find(frame, region, t.threshold.unwrap_or(0.9)) {
+            Some(m) => {
+                if t.store {
+                    self.found = Some((
+                        m.x + template.width as i32 / 2,
+                        m.y + template.height as i32 / 2,
+                    ));
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 校验血条节点, 填充百分比满足 below/above 时成立
+    pub fn check_bar(&mut self, b: &Bar) -> bool {
+        let gauge = match BarGauge::parse(b) {
+            Ok(gauge) => gauge,
+            Err(e) => {
+                eprintln!("血条配置有误: {}", e);
+                return false;
+            }
+        };
+        let source = match screen() {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("取色失败: {}", e);
+                return false;
+            }
+        };
+        let frame = match self.frames.frame(source.as_ref(), Instant::now()) {
+            Ok(Some(frame)) => frame,
+            Ok(None) => return false,
+            Err(e) => {
+                eprintln!("截图失败: {}", e);
+                return false;
+            }
+        };
+        gauge.check(|x, y| frame.pixel(x, y))
+    }
+
+    /// 校验数字节点, 识别出的数字满足 below/above/equal 时成立
+    pub fn check_digits(&mut self, d: &Digits) -> bool {
+        let reader = match DigitReader::parse(d) {
+            Ok(reader) => reader,
+            Err(e) => {
+                eprintln!("数字识别配置有误: {}", e);
+                return false;
+            }
+        };
+        // 每个字形集只读取一次, 读取失败也只提示一次
+        if !self.glyphs.contains_key(&d.digits) {
+            let glyphs = GlyphSet::load(&d.digits)
+                .map_err(|e| eprintln!("{}", e))
+                .ok();
+            self.glyphs.insert(d.digits.clone(), glyphs);
+        }
+        let glyphs = match self.glyphs.get(&d.digits) {
+            Some(Some(glyphs)) => glyphs,
+            _ => return false,
+        };
+        let source = match screen() {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("取色失败: {}", e);
+                return false;
+            }
+        };
+        let frame = match self.frames.frame(source.as_ref(), Instant::now()) {
+            Ok(Some(frame)) => frame,
+            Ok(None) => return false,
+            Err(e) => {
+                eprintln!("截图失败: {}", e);
+                return false;
+            }
+        };
+        reader
+            .read(frame, glyphs)
+            .is_some_and(|value| number_holds(value, d))
+    }
+}
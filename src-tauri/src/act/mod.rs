@@ -1,4 +1,7 @@
 pub mod action;
-pub mod keyboard;
-pub mod mouse;
+pub mod context;
+pub mod press;
+pub mod schedule;
 pub mod send;
+pub mod trigger;
+pub mod watch;
@@ -0,0 +1,134 @@
+use std::time::{Duration, Instant};
+
+/// 按键识别结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Press {
+    Tap,       // 轻按: 在长按时间内抬起
+    HoldStart, // 长按开始: 按住超过长按时间
+    HoldEnd,   // 长按结束: 长按后抬起
+    LateHold,  // 超过长按时间后抬起, 但还没来得及开始长按
+}
+
+/**
+ * 根据长按时间区分轻按与长按
+ * 只记录按下时间, 由调用方在 deadline 到达时调用 poll
+ */
+pub struct TapHold {
+    threshold: Duration,
+    pressed_at: Option<Instant>,
+    holding: bool,
+}
+
+impl TapHold {
+    pub fn new(threshold: Duration) -> Self {
+        TapHold {
+            threshold,
+            pressed_at: None,
+            holding: false,
+        }
+    }
+
+    pub fn press(&mut self, now: Instant) {
+        if self.pressed_at.is_none() {
+            self.pressed_at = Some(now);
+            self.holding = false;
+        }
+    }
+
+    pub fn release(&mut self, now: Instant) -> Option<Press> {
+        let pressed_at = self.pressed_at.take()?;
+        if self.holding {
+            self.holding = false;
+            Some(Press::HoldEnd)
+        } else if now.duration_since(pressed_at) < self.threshold {
+            Some(Press::Tap)
+        } else {
+            // 超过长按时间但还没来得及开始长按, 由调用方把长按的开始和结束一起处理
+            Some(Press::LateHold)
+        }
+    }
+
+    pub fn poll(&mut self, now: Instant) -> Option<Press> {
+        match self.pressed_at {
+            Some(pressed_at)
+                if !self.holding && now.duration_since(pressed_at) >= self.threshold =>
+            {
+                self.holding = true;
+                Some(Press::HoldStart)
+            }
+            _ => None,
+        }
+    }
+
+    /// 下一次需要调用 poll 的时间
+    pub fn deadline(&self) -> Option<Instant> {
+        match self.pressed_at {
+            Some(pressed_at) if !self.holding => Some(pressed_at + self.threshold),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * 双击识别, 两次按下间隔不超过 window 才算双击
+ * 双击后重新计数, 连按三次只算一次双击
+ */
+pub struct DoubleTap {
+    window: Duration,
+    last: Option<Instant>,
+}
+
+impl DoubleTap {
+    pub fn new(window: Duration) -> Self {
+        DoubleTap { window, last: None }
+    }
+
+    /// 按下时调用, 构成双击时返回 true
+    pub fn press(&mut self, now: Instant) -> bool {
+        match self.last.take() {
+            Some(last) if now.duration_since(last) <= self.window => true,
+            _ => {
+                self.last = Some(now);
+                false
+            }
+        }
+    }
+}
+
+/**
+ * 切换模式的自动停止, 开启超过 limit 后停止
+ * limit 为空时不自动停止
+ */
+pub struct AutoOff {
+    limit: Option<Duration>,
+    since: Option<Instant>,
+}
+
+impl AutoOff {
+    pub fn new(limit: Option<Duration>) -> Self {
+        AutoOff { limit, since: None }
+    }
+
+    pub fn start(&mut self, now: Instant) {
+        self.since = Some(now);
+    }
+
+    pub fn stop(&mut self) {
+        self.since = None;
+    }
+
+    /// 到达自动停止时间时返回 true, 只返回一次
+    pub fn poll(&mut self, now: Instant) -> bool {
+        match self.deadline() {
+            Some(deadline) if now >= deadline => {
+                self.since = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        Some(self.since? + self.limit?)
+    }
+}
@@ -0,0 +1,70 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::global::model::Timer;
+
+/// 时钟, 定时触发通过它获取时间, 测试时可以替换为虚拟时钟
+pub trait Clock {
+    fn now(&self) -> Instant;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/**
+ * 定时触发的执行计划
+ * 错过的执行时间(暂停或上一次执行未结束)直接跳过, 不会补执行
+ */
+pub struct Schedule {
+    interval: Option<Duration>,
+    next: Option<Instant>,
+}
+
+impl Schedule {
+    pub fn new(timer: &Timer, start: Instant) -> Self {
+        let interval = timer
+            .interval
+            .filter(|ms| *ms > 0)
+            .map(Duration::from_millis);
+        // 未设置延迟时, 第一次执行在一个间隔之后
+        let first = timer.delay.map(Duration::from_millis).or(interval);
+        Schedule {
+            interval,
+            next: first.map(|d| start + d),
+        }
+    }
+
+    /// 到达执行时间时返回 true, 并计算下一次执行时间
+    pub fn due(&mut self, now: Instant) -> bool {
+        match self.next {
+            Some(next) if next <= now => {
+                self.next = self.interval.map(|interval| {
+                    let mut next = next + interval;
+                    while next <= now {
+                        next += interval;
+                    }
+                    next
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 下一次执行时间, 单次执行完成后为 None
+    pub fn next_run(&self) -> Option<Instant> {
+        self.next
+    }
+}
@@ -1,14 +1,28 @@
+#[cfg(windows)]
 use std::{thread, time};
+#[cfg(windows)]
 use windows::Win32::UI::Input::KeyboardAndMouse::{
-    SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP, KEYEVENTF_SCANCODE,
-    MAPVK_VK_TO_VSC, VIRTUAL_KEY,
+    SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, INPUT_MOUSE, KEYBDINPUT, KEYEVENTF_KEYUP,
+    KEYEVENTF_SCANCODE, MAPVK_VK_TO_VSC, MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP,
+    MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP, MOUSEINPUT, MOUSE_EVENT_FLAGS, VIRTUAL_KEY,
 };
+#[cfg(windows)]
+use windows::Win32::UI::WindowsAndMessaging::SetCursorPos;
+
+#[cfg(windows)]
+use crate::input::{
+    code::{MOUSE_LEFT, MOUSE_RIGHT},
+    hook::mark_injected,
+};
+
+// 模拟输入只支持 Windows, 其他系统下执行到按键或鼠标元素时返回错误
 
 /// 模拟键盘按键点击
 ///
 /// # 参数
 /// - `vk_code`: 虚拟键码（VIRTUAL_KEY）
 /// - `key_up_delay`: 按下到抬起之间的延迟（毫秒）
+#[cfg(windows)]
 pub fn simulate_key(vk_code: u32, key_up_delay: u32) -> Result<(), Box<dyn std::error::Error>> {
     use windows::Win32::UI::Input::KeyboardAndMouse::MapVirtualKeyW;
 
@@ -44,7 +58,8 @@ pub fn simulate_key(vk_code: u32, key_up_delay: u32) -> Result<(), Box<dyn std::
             },
         };
 
-        // 发送按下
+        // 发送按下, 先记录以便钩子放行
+        mark_injected(vk_code, true);
         let sent_down = SendInput(&[input_down], std::mem::size_of::<INPUT>() as i32);
         if sent_down == 0 {
             return Err("SendInput 按下事件失败".into());
@@ -54,6 +69,7 @@ pub fn simulate_key(vk_code: u32, key_up_delay: u32) -> Result<(), Box<dyn std::
         thread::sleep(time::Duration::from_millis(key_up_delay.into()));
 
         // 发送抬起
+        mark_injected(vk_code, false);
         let sent_up = SendInput(&[input_up], std::mem::size_of::<INPUT>() as i32);
         if sent_up == 0 {
             return Err("SendInput 抬起事件失败".into());
@@ -64,3 +80,66 @@ pub fn simulate_key(vk_code: u32, key_up_delay: u32) -> Result<(), Box<dyn std::
     }
     Ok(())
 }
+
+/// 模拟鼠标操作
+///
+/// # 参数
+/// - `x`, `y`: 屏幕坐标
+/// - `action`: move 只移动, left 左键单击, right 右键单击
+#[cfg(windows)]
+pub fn simulate_mouse(x: i32, y: i32, action: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let (code, down, up) = match action {
+        "move" => (0, None, None),
+        "left" => (
+            MOUSE_LEFT,
+            Some(MOUSEEVENTF_LEFTDOWN),
+            Some(MOUSEEVENTF_LEFTUP),
+        ),
+        "right" => (
+            MOUSE_RIGHT,
+            Some(MOUSEEVENTF_RIGHTDOWN),
+            Some(MOUSEEVENTF_RIGHTUP),
+        ),
+        _ => return Err(format!("不支持的鼠标操作: {}", action).into()),
+    };
+
+    unsafe {
+        SetCursorPos(x, y)?;
+
+        let button = |flags: MOUSE_EVENT_FLAGS| INPUT {
+            r#type: INPUT_MOUSE,
+            Anonymous: INPUT_0 {
+                mi: MOUSEINPUT {
+                    dx: 0,
+                    dy: 0,
+                    mouseData: 0,
+                    dwFlags: flags,
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        };
+        if let (Some(down), Some(up)) = (down, up) {
+            mark_injected(code, true);
+            mark_injected(code, false);
+            let sent = SendInput(
+                &[button(down), button(up)],
+                std::mem::size_of::<INPUT>() as i32,
+            );
+            if sent == 0 {
+                return Err("SendInput 鼠标事件失败".into());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+pub fn simulate_key(vk_code: u32, _key_up_delay: u32) -> Result<(), Box<dyn std::error::Error>> {
+    Err(format!("当前系统不支持模拟按键: {}", vk_code).into())
+}
+
+#[cfg(not(windows))]
+pub fn simulate_mouse(x: i32, y: i32, action: &str) -> Result<(), Box<dyn std::error::Error>> {
+    Err(format!("当前系统不支持模拟鼠标: {} {},{}", action, x, y).into())
+}
@@ -0,0 +1,427 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::RecvTimeoutError,
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    act::{
+        action::run_element,
+        context::RunContext,
+        press::{AutoOff, DoubleTap, Press, TapHold},
+        schedule::{Clock, Schedule, SystemClock},
+        watch::register,
+    },
+    global::{
+        global::{ACTION_TYPE, DOUBLE_TAP_TIME, HOLD_ON_TIME, MODE_CLOSE, STOP_ACTION, TIME_WITE},
+        model::{Children, CoordinateSpace, Elements, Timer, Trigger, TriggerMode},
+    },
+    input::{
+        code::{is_supported, is_wheel_code},
+        hook::subscribe,
+    },
+    rgb::coord::{transform_children, window_client_rect, WindowTracker},
+    start::status::{set_next_run, set_running},
+};
+
+/**
+ * 监听规则的触发键(键盘或鼠标)或定时触发
+ * id: 规则编号, 用于记录运行状态
+ */
+pub fn trigger_type(id: usize, elements: Elements) {
+    {
+        let mut mode_close = MODE_CLOSE.lock().unwrap();
+        *mode_close = false;
+    }
+    // 定时触发不需要按键
+    if elements.timer.is_some() {
+        timer(id, elements);
+        return;
+    }
+    // 条件触发由检测线程统一处理
+    if elements.watch.is_some() {
+        register(id, elements);
+        return;
+    }
+    let action_type = *ACTION_TYPE.lock().unwrap();
+    let triggers = resolve_triggers(&elements, action_type);
+    if !triggers.is_empty() {
+        listen(id, elements, triggers);
+    }
+}
+
+/**
+ * 规则的触发键及其模式, 不支持的触发码会被忽略
+ * 没有配置 triggers 时使用 header 的按键, 模式由轻按流程和全局操作方式决定
+ */
+pub fn resolve_triggers(elements: &Elements, action_type: u32) -> Vec<(Trigger, TriggerMode)> {
+    let default_mode = if elements.tap_children.is_some() {
+        TriggerMode::TapHold
+    } else if action_type == 2 {
+        TriggerMode::Toggle
+    } else {
+        TriggerMode::Hold
+    };
+    let triggers = match &elements.triggers {
+        Some(triggers) if !triggers.is_empty() => triggers.clone(),
+        _ => vec![Trigger {
+            code: elements.header.elements_code,
+            mode: None,
+            auto_off: None,
+            window: None,
+            suppress: false,
+        }],
+    };
+    triggers
+        .into_iter()
+        .filter(|t| is_supported(t.code))
+        .map(|t| {
+            let mode = t.mode.unwrap_or(default_mode);
+            (t, mode)
+        })
+        .collect()
+}
+
+pub fn mode_name(mode: TriggerMode) -> &'static str {
+    match mode {
+        TriggerMode::Hold => "长按",
+        TriggerMode::Toggle => "单击",
+        TriggerMode::Once => "单次",
+        TriggerMode::DoubleTap => "双击",
+        TriggerMode::TapHold => "轻按/长按",
+    }
+}
+
+// 单个触发键的识别状态
+enum Detect {
+    Hold,
+    Toggle(AutoOff),
+    Once,
+    DoubleTap(DoubleTap),
+    TapHold(TapHold),
+}
+
+impl Detect {
+    fn new(trigger: &Trigger, mode: TriggerMode) -> Self {
+        match mode {
+            TriggerMode::Hold => Detect::Hold,
+            TriggerMode::Toggle => Detect::Toggle(AutoOff::new(
+                trigger.auto_off.filter(|s| *s > 0).map(Duration::from_secs),
+            )),
+            TriggerMode::Once => Detect::Once,
+            TriggerMode::DoubleTap => Detect::DoubleTap(DoubleTap::new(Duration::from_millis(
+                trigger.window.unwrap_or(*DOUBLE_TAP_TIME),
+            ))),
+            TriggerMode::TapHold => Detect::TapHold(TapHold::new(Duration::from_millis(
+                *HOLD_ON_TIME.lock().unwrap(),
+            ))),
+        }
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        match self {
+            Detect::Toggle(auto_off) => auto_off.deadline(),
+            Detect::TapHold(press) => press.deadline(),
+            _ => None,
+        }
+    }
+}
+
+// 识别结果
+enum Fire {
+    Run { tap: bool, repeat: bool }, // 执行流程, tap 为 true 时执行轻按流程
+    Stop,                            // 停止由该触发键开始的流程
+}
+
+// 处理触发键的按下或抬起, mine: 正在执行的流程由该触发键开始
+// 按下时除了正在执行自己开始的流程, 都会替换正在执行的流程
+fn on_event(
+    detect: &mut Detect,
+    code: u32,
+    pressed: bool,
+    time: Instant,
+    mine: bool,
+) -> Option<Fire> {
+    // 滚轮没有按住的过程, 每滚动一次执行一遍
+    let wheel = is_wheel_code(code);
+    match detect {
+        Detect::Hold if pressed => (!mine).then_some(Fire::Run {
+            tap: false,
+            repeat: !wheel,
+        }),
+        Detect::Hold if !wheel => Some(Fire::Stop),
+        Detect::Toggle(auto_off) if pressed => {
+            if mine {
+                auto_off.stop();
+                Some(Fire::Stop)
+            } else {
+                auto_off.start(time);
+                Some(Fire::Run {
+                    tap: false,
+                    repeat: true,
+                })
+            }
+        }
+        Detect::Once if pressed => (!mine).then_some(Fire::Run {
+            tap: false,
+            repeat: false,
+        }),
+        Detect::DoubleTap(double) if pressed => double.press(time).then_some(Fire::Run {
+            tap: false,
+            repeat: false,
+        }),
+        Detect::TapHold(press) if pressed => {
+            press.press(time);
+            None
+        }
+        Detect::TapHold(press) => match press.release(time) {
+            Some(Press::Tap) => Some(Fire::Run {
+                tap: true,
+                repeat: false,
+            }),
+            Some(Press::HoldEnd) => Some(Fire::Stop),
+            // 长按已经结束, 主流程只执行一遍
+            Some(Press::LateHold) => Some(Fire::Run {
+                tap: false,
+                repeat: false,
+            }),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// 到达长按时间或自动停止时间
+fn on_poll(detect: &mut Detect, now: Instant) -> Option<Fire> {
+    match detect {
+        Detect::Toggle(auto_off) => auto_off.poll(now).then_some(Fire::Stop),
+        Detect::TapHold(press) => match press.poll(now) {
+            Some(Press::HoldStart) => Some(Fire::Run {
+                tap: false,
+                repeat: true,
+            }),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// 按键触发监听: 一条规则的所有触发键共用一个执行线程, 后触发的流程会替换正在执行的流程
+fn listen(id: usize, elements: Elements, triggers: Vec<(Trigger, TriggerMode)>) {
+    thread::spawn(move || {
+        let events = subscribe();
+        let mut detects: Vec<(u32, Detect)> = triggers
+            .iter()
+            .map(|(trigger, mode)| (trigger.code, Detect::new(trigger, *mode)))
+            .collect();
+        let stop_flag = Arc::new(AtomicBool::new(true));
+        let mut worker: Option<thread::JoinHandle<()>> = None;
+        let mut owner: Option<usize> = None; // 正在执行的流程由哪个触发键开始
+
+        loop {
+            // 判断是否关闭
+            if *MODE_CLOSE.lock().unwrap() {
+                break;
+            }
+            // 暂停时停止正在执行的流程
+            let paused = *STOP_ACTION.lock().unwrap();
+            if paused {
+                stop_worker(&stop_flag, &mut worker);
+            }
+
+            // 按住或切换期间需要在长按时间、自动停止时间到达时醒来
+            let mut timeout = Duration::from_millis(*TIME_WITE);
+            if let Some(deadline) = detects.iter().filter_map(|(_, d)| d.deadline()).min() {
+                timeout = timeout.min(deadline.saturating_duration_since(Instant::now()));
+            }
+
+            let idle = worker.as_ref().map(|t| t.is_finished()).unwrap_or(true);
+            let mut fired = Vec::new();
+            match events.recv_timeout(timeout) {
+                Ok(event) => {
+                    for (i, (code, detect)) in detects.iter_mut().enumerate() {
+                        if *code != event.code {
+                            continue;
+                        }
+                        let mine = !idle && owner == Some(i);
+                        if let Some(fire) = on_event(detect, *code, event.pressed, event.time, mine)
+                        {
+                            fired.push((i, fire));
+                        }
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            let now = Instant::now();
+            for (i, (_, detect)) in detects.iter_mut().enumerate() {
+                if let Some(fire) = on_poll(detect, now) {
+                    fired.push((i, fire));
+                }
+            }
+
+            for (i, fire) in fired {
+                match fire {
+                    Fire::Run { tap, repeat } => {
+                        if paused {
+                            continue;
+                        }
+                        stop_worker(&stop_flag, &mut worker);
+                        stop_flag.store(false, Ordering::Relaxed);
+                        let children = if tap {
+                            elements.tap_children.clone()
+                        } else {
+                            elements.children.clone()
+                        };
+                        worker = Some(spawn_worker(
+                            id,
+                            children,
+                            elements.frame_age,
+                            elements.coordinates.clone(),
+                            stop_flag.clone(),
+                            repeat,
+                        ));
+                        owner = Some(i);
+                    }
+                    // 只停止自己开始的流程, 避免松开一个键时打断另一个键开始的流程
+                    Fire::Stop if owner == Some(i) => stop_worker(&stop_flag, &mut worker),
+                    Fire::Stop => {}
+                }
+            }
+        }
+
+        // 退出前确保线程结束
+        stop_worker(&stop_flag, &mut worker);
+    });
+}
+
+// 定时触发: 按间隔或延迟执行流程, 暂停期间到达的执行时间直接跳过
+fn timer(id: usize, elements: Elements) {
+    thread::spawn(move || {
+        run_timer(
+            id,
+            &elements.timer.clone().unwrap_or_default(),
+            &SystemClock,
+            || *MODE_CLOSE.lock().unwrap(),
+            || *STOP_ACTION.lock().unwrap(),
+            |stop_flag| {
+                spawn_worker(
+                    id,
+                    elements.children.clone(),
+                    elements.frame_age,
+                    elements.coordinates.clone(),
+                    stop_flag,
+                    false,
+                )
+            },
+        );
+    });
+}
+
+/**
+ * 定时触发的循环, closed 返回 true 时结束
+ * 到达执行时间且没有暂停时调用 spawn 启动执行线程, 上一次执行还没结束时跳过本次
+ */
+pub fn run_timer(
+    id: usize,
+    timer: &Timer,
+    clock: &impl Clock,
+    closed: impl Fn() -> bool,
+    paused: impl Fn() -> bool,
+    mut spawn: impl FnMut(Arc<AtomicBool>) -> thread::JoinHandle<()>,
+) {
+    let mut schedule = Schedule::new(timer, clock.now());
+    let stop_flag = Arc::new(AtomicBool::new(true));
+    let mut worker: Option<thread::JoinHandle<()>> = None;
+
+    loop {
+        // 判断是否关闭
+        if closed() {
+            break;
+        }
+        let paused = paused();
+        if paused {
+            stop_worker(&stop_flag, &mut worker);
+        }
+
+        let now = clock.now();
+        // 上一次执行还没结束时跳过本次
+        let idle = worker.as_ref().map(|t| t.is_finished()).unwrap_or(true);
+        if schedule.due(now) && !paused && idle {
+            stop_flag.store(false, Ordering::Relaxed);
+            worker = Some(spawn(stop_flag.clone()));
+        }
+        set_next_run(id, schedule.next_run());
+
+        let mut wait = Duration::from_millis(*TIME_WITE);
+        if let Some(next) = schedule.next_run() {
+            wait = wait.min(next.saturating_duration_since(now));
+        }
+        clock.sleep(wait);
+    }
+
+    // 退出前确保线程结束
+    stop_worker(&stop_flag, &mut worker);
+    set_next_run(id, None);
+}
+
+// 启动执行线程, repeat 为 true 时持续循环执行, 直到停止标志被设置
+// frame_age: 取色截图最长复用时间ms, 为空时每轮截图一次
+// coordinates: 相对窗口记录坐标时, 每轮执行前按窗口当前的位置换算
+pub fn spawn_worker(
+    id: usize,
+    children: Option<Vec<Children>>,
+    frame_age: Option<u64>,
+    coordinates: Option<CoordinateSpace>,
+    stop_flag: Arc<AtomicBool>,
+    repeat: bool,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        set_running(id, true);
+        let mut window = coordinates.as_ref().and_then(WindowTracker::new);
+        let mut resolved = children.clone();
+        let mut ctx = RunContext::new(resolved.as_deref().unwrap_or_default(), frame_age);
+        while !stop_flag.load(Ordering::Relaxed) {
+            if let Some(tracker) = &mut window {
+                match tracker.update(window_client_rect) {
+                    Ok(Some(t)) => {
+                        resolved = children.clone();
+                        if let Some(resolved) = &mut resolved {
+                            transform_children(resolved, &t);
+                        }
+                        ctx = RunContext::new(resolved.as_deref().unwrap_or_default(), frame_age);
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        eprintln!("坐标换算失败: {}", e);
+                        break;
+                    }
+                }
+            }
+            if let Some(children) = &resolved {
+                ctx.begin_cycle();
+                let result = run_element(children.clone(), stop_flag.clone(), &mut ctx);
+                if result.is_err() {
+                    eprintln!("Error running element: {:?}", result.err());
+                }
+            }
+            if !repeat {
+                break;
+            }
+            // 控制循环频率
+            thread::sleep(Duration::from_millis(*TIME_WITE));
+        }
+        set_running(id, false);
+    })
+}
+
+pub fn stop_worker(stop_flag: &Arc<AtomicBool>, worker: &mut Option<thread::JoinHandle<()>>) {
+    stop_flag.store(true, Ordering::Relaxed);
+    if let Some(handle) = worker.take() {
+        let _ = handle.join();
+    }
+}
@@ -0,0 +1,244 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+
+use crate::{
+    act::trigger::{spawn_worker, stop_worker},
+    global::{
+        global::{MODE_CLOSE, STOP_ACTION, WATCH_TIME},
+        model::{Children, CoordinateSpace, Elements, Watch, WatchCondition},
+    },
+    memory::attach::target,
+    rgb::{
+        check::check_color_distance,
+        coord::{transform_condition, window_client_rect, WindowTracker},
+    },
+};
+
+/**
+ * 条件状态, 连续 confirm 次检测结果与当前状态不同才切换, 避免抖动
+ */
+pub struct ConditionState {
+    active: bool,
+    streak: u32,
+    confirm: u32,
+}
+
+impl ConditionState {
+    pub fn new(confirm: u32) -> Self {
+        ConditionState {
+            active: false,
+            streak: 0,
+            confirm: confirm.max(1),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// 更新检测结果, 状态切换时返回新状态
+    pub fn update(&mut self, value: bool) -> Option<bool> {
+        if value == self.active {
+            self.streak = 0;
+            return None;
+        }
+        self.streak += 1;
+        if self.streak >= self.confirm {
+            self.active = value;
+            self.streak = 0;
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
+
+/// 内力是否低于阈值, 条件成立后需要高于 release 才结束
+pub fn mana_active(mana: usize, below: u32, release: Option<u32>, active: bool) -> bool {
+    let threshold = if active {
+        release.unwrap_or(below).max(below)
+    } else {
+        below
+    };
+    mana <= threshold as usize
+}
+
+/// 检测条件当前是否成立
+pub fn evaluate(condition: &WatchCondition, active: bool) -> bool {
+    match condition {
+        WatchCondition::Pixel {
+            coordinate,
+            rgb,
+            deviation,
+            mismatch,
+        } => {
+            let matched = std::panic::catch_unwind(|| {
+                check_color_distance(rgb.clone(), coordinate.clone(), *deviation)
+            })
+            .unwrap_or_else(|_| {
+                eprintln!("Color check panicked, using false as default");
+                false
+            });
+            matched != *mismatch
+        }
+        WatchCondition::Mana { below, release } => match target().process() {
+            Ok(reader) => match reader.read_mana() {
+                Ok(mana) => mana_active(mana, *below, *release, active),
+                Err(e) => {
+                    eprintln!("Error reading inner power: {:?}", e);
+                    false
+                }
+            },
+            Err(e) => {
+                eprintln!("Failed to open process for mana: {:?}", e);
+                false
+            }
+        },
+    }
+}
+
+struct Watched {
+    id: usize,
+    watch: Watch,
+    children: Option<Vec<Children>>,
+    frame_age: Option<u64>,
+    coordinates: Option<CoordinateSpace>,
+    window: Option<WindowTracker>, // 相对窗口记录坐标时, 每次检测前换算条件
+    condition: WatchCondition,     // 换算后的条件
+    state: ConditionState,
+    next_check: Instant,
+    stop_flag: Arc<AtomicBool>,
+    worker: Option<thread::JoinHandle<()>>,
+    removed: bool, // 已被移除, 检测线程不再启动流程
+}
+
+impl Watched {
+    fn remove(&mut self) {
+        self.removed = true;
+        stop_worker(&self.stop_flag, &mut self.worker);
+    }
+}
+
+lazy_static! {
+    static ref WATCHED: Mutex<Vec<Arc<Mutex<Watched>>>> = Mutex::new(Vec::new()); // 已注册的条件
+}
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+/**
+ * 注册条件触发的规则, 第一次注册时启动检测线程
+ */
+pub fn register(id: usize, elements: Elements) {
+    let watch = match elements.watch {
+        Some(watch) => watch,
+        None => return,
+    };
+    let mut watched = WATCHED.lock().unwrap();
+    watched.push(Arc::new(Mutex::new(Watched {
+        id,
+        state: ConditionState::new(watch.confirm.unwrap_or(1)),
+        window: elements.coordinates.as_ref().and_then(WindowTracker::new),
+        condition: watch.condition.clone(),
+        watch,
+        children: elements.children,
+        frame_age: elements.frame_age,
+        coordinates: elements.coordinates,
+        next_check: Instant::now(),
+        stop_flag: Arc::new(AtomicBool::new(true)),
+        worker: None,
+        removed: false,
+    })));
+    if !RUNNING.swap(true, Ordering::Relaxed) {
+        thread::spawn(watch_loop);
+    }
+}
+
+/// 停止并移除所有条件
+pub fn clear() {
+    // 先移出列表再停止, 等待流程结束时不阻塞注册
+    let removed = std::mem::take(&mut *WATCHED.lock().unwrap());
+    for w in removed {
+        w.lock().unwrap().remove();
+    }
+}
+
+fn watch_loop() {
+    loop {
+        // 只在持有锁时复制条件列表, 检测条件和等待流程结束时不持有锁
+        let watched = {
+            // 在持有锁时判断关闭, 避免与重新开启时的注册交错
+            let mut watched = WATCHED.lock().unwrap();
+            if *MODE_CLOSE.lock().unwrap() {
+                let removed = std::mem::take(&mut *watched);
+                RUNNING.store(false, Ordering::Relaxed);
+                drop(watched);
+                for w in removed {
+                    w.lock().unwrap().remove();
+                }
+                break;
+            }
+            watched.clone()
+        };
+        let paused = *STOP_ACTION.lock().unwrap();
+
+        let now = Instant::now();
+        for w in watched {
+            let mut w = w.lock().unwrap();
+            if w.removed {
+                continue;
+            }
+            // 暂停期间停止执行, 恢复后重新检测
+            if paused {
+                let w = &mut *w;
+                stop_worker(&w.stop_flag, &mut w.worker);
+                w.state = ConditionState::new(w.watch.confirm.unwrap_or(1));
+                continue;
+            }
+            if now < w.next_check {
+                continue;
+            }
+            w.next_check = now + Duration::from_millis(w.watch.rate.unwrap_or(*WATCH_TIME));
+
+            let w = &mut *w;
+            // 窗口移动后重新换算坐标, 找不到窗口时不检测
+            if let Some(tracker) = &mut w.window {
+                match tracker.update(window_client_rect) {
+                    Ok(Some(t)) => {
+                        w.condition = w.watch.condition.clone();
+                        transform_condition(&mut w.condition, &t);
+                    }
+                    Ok(None) => {}
+                    Err(_) => continue,
+                }
+            }
+            let value = evaluate(&w.condition, w.state.is_active());
+            // 边沿触发只执行一次, 电平触发在条件成立期间循环执行
+            match w.state.update(value) {
+                Some(true) if w.worker.as_ref().map(|t| t.is_finished()).unwrap_or(true) => {
+                    w.stop_flag.store(false, Ordering::Relaxed);
+                    w.worker = Some(spawn_worker(
+                        w.id,
+                        w.children.clone(),
+                        w.frame_age,
+                        w.coordinates.clone(),
+                        w.stop_flag.clone(),
+                        !w.watch.edge,
+                    ));
+                }
+                Some(false) if !w.watch.edge => stop_worker(&w.stop_flag, &mut w.worker),
+                _ => {}
+            }
+        }
+
+        // 各条件按自己的检测间隔执行, 这里只控制最小粒度
+        thread::sleep(Duration::from_millis(10));
+    }
+}
@@ -1,8 +1,146 @@
-use crate::rgb::pick::mouse_rgb;
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    global::model::{Color, ColorMetric, Digits},
+    rgb::{
+        check::parse_rgb,
+        digits::{glyph_set_path, save_glyphs, DigitReader, GlyphSet, Mask},
+        monitor::Monitor,
+        pattern::parse_region,
+        pick::{
+            cancel_pick, mouse_rgb, mouse_rgb_samples, pick, picked, ColorSample, PickOptions,
+            PickResult,
+        },
+        probe::{unwatch_pixels, watch_pixels},
+        source::screen,
+        template::save_template,
+    },
+};
+
+#[tauri::command]
+// 鼠标位置的rgb值, 按 F1 取色, Esc 取消
+pub async fn get_mouse_rgb() -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(mouse_rgb)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+// 取色, 取色过程中通过 color_picker 事件推送鼠标位置和颜色, 返回颜色和邻域统计
+pub async fn pick_color(
+    app: AppHandle,
+    options: Option<PickOptions>,
+) -> Result<PickResult, String> {
+    let options = options.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        let outcome = pick(&options, |update| {
+            let _ = app.emit("color_picker", update);
+        })?;
+        picked(outcome)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+// 取消正在进行的取色
+pub fn cancel_color_picker() {
+    cancel_pick();
+}
+
+#[tauri::command]
+// 采样取色, 返回平均颜色和建议的偏差, samples 默认 20 次, interval 默认 50ms
+// 和 pick_color 一样通过 color_picker 事件推送颜色, 可以用 cancel_color_picker 取消
+pub async fn get_mouse_rgb_samples(
+    app: AppHandle,
+    metric: Option<ColorMetric>,
+    samples: Option<u32>,
+    interval: Option<u64>,
+) -> Result<ColorSample, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        mouse_rgb_samples(
+            metric.unwrap_or_default(),
+            samples.unwrap_or(20),
+            interval.unwrap_or(50),
+            |update| {
+                let _ = app.emit("color_picker", update);
+            },
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+// 截取屏幕区域保存为模板, region: "x,y,宽,高", 返回保存的文件路径
+pub fn save_screen_template(name: String, region: String) -> Result<String, String> {
+    let (x, y, width, height) =
+        parse_region(&region).ok_or_else(|| format!("区域有误: {}", region))?;
+    let frame = screen()?.region(x, y, width, height)?;
+    let path = save_template(&name, &frame)?;
+    Ok(path.display().to_string())
+}
+
+#[tauri::command]
+// 截取屏幕区域保存数字字形, text 为区域内从左到右显示的数字, 按 rgb 颜色分割字形
+pub fn save_digit_glyphs(
+    name: String,
+    text: String,
+    region: String,
+    rgb: String,
+    tolerance: Option<f64>,
+    metric: Option<ColorMetric>,
+) -> Result<Vec<String>, String> {
+    let region = parse_region(&region).ok_or_else(|| format!("区域有误: {}", region))?;
+    let color = parse_rgb(&rgb).ok_or_else(|| format!("颜色有误: {}", rgb))?;
+    let (x, y, width, height) = region;
+    let frame = screen()?.region(x, y, width, height)?;
+    let mask = Mask::from_frame(
+        &frame,
+        region,
+        color,
+        metric.unwrap_or_default(),
+        tolerance.unwrap_or_default(),
+        None,
+    );
+    let paths = save_glyphs(glyph_set_path(&name)?, &text, &mask)?;
+    Ok(paths.iter().map(|p| p.display().to_string()).collect())
+}
+
+#[tauri::command]
+// 识别数字节点区域内的数字, 用于编辑规则时检查字形, 无法识别时返回空
+pub fn read_screen_digits(digits: Digits) -> Result<Option<i64>, String> {
+    let reader = DigitReader::parse(&digits)?;
+    let glyphs = GlyphSet::load(&digits.digits)?;
+    let (x, y, width, height) = reader.region;
+    let frame = screen()?.region(x, y, width, height)?;
+    Ok(reader.read(&frame, &glyphs))
+}
+
+#[tauri::command]
+// 显示器布局, 坐标为虚拟桌面坐标, 主显示器左上角为 (0, 0)
+pub fn list_monitors() -> Result<Vec<Monitor>, String> {
+    screen()?.monitors()
+}
+
+#[tauri::command]
+// 订阅取色节点坐标的颜色, 每隔 rate 毫秒(默认 100)通过 pixel_monitor 事件推送, 返回订阅id
+pub fn watch_pixel_colors(
+    app: AppHandle,
+    probes: Vec<Color>,
+    rate: Option<u64>,
+) -> Result<u64, String> {
+    let source = screen()?;
+    Ok(watch_pixels(
+        source,
+        probes,
+        rate.unwrap_or(100),
+        move |frame| app.emit("pixel_monitor", frame).is_ok(),
+    ))
+}
 
 #[tauri::command]
-// 鼠标位置的rgb值
-pub fn get_mouse_rgb() -> String {
-    let rbg = mouse_rgb();
-    rbg
+// 取消订阅
+pub fn unwatch_pixel_colors(id: u64) -> bool {
+    unwatch_pixels(id)
 }
@@ -4,7 +4,11 @@ use serde::Serialize;
 
 use crate::{
     global::{global::ACTION_TYPE, model::Elements},
-    start::start::{close, start, stop},
+    start::{
+        conflict::{find_conflicts, Conflict},
+        start::{close, start, stop},
+        status::{snapshot, RuntimeStatus},
+    },
 };
 #[derive(Serialize)]
 struct Res<T: Any> {
@@ -15,26 +19,49 @@ struct Res<T: Any> {
 
 #[tauri::command]
 pub async fn run(elements: Vec<Elements>, t: bool) -> String {
-    let mut response: Res<()> = Res {
+    let mut response: Res<Vec<Conflict>> = Res {
         code: 200,
         message: String::from("开启成功"),
-        data: (),
+        data: Vec::new(),
     };
     if t {
         if elements.len() == 0 {
             response.message = String::from("没有可用规则");
         } else {
-            start(elements, t);
-            stop()
+            match start(elements, t) {
+                Ok(()) => stop(),
+                Err(conflicts) => {
+                    response.code = 409;
+                    response.message = String::from("触发键冲突, 规则未开启");
+                    response.data = conflicts;
+                }
+            }
         }
     } else {
-        start(elements, t);
+        let _ = start(elements, t);
         response.message = String::from("关闭");
     }
     let json_str = serde_json::to_string(&response).expect("Failed to serialize");
     json_str
 }
 
+#[tauri::command]
+// 检查规则之间以及规则与全局快捷键之间的触发键冲突
+pub fn check_conflicts(elements: Vec<Elements>) -> String {
+    let conflicts = find_conflicts(&elements);
+    let response = Res {
+        code: 200,
+        message: if conflicts.is_empty() {
+            String::from("没有冲突")
+        } else {
+            format!("发现{}处冲突", conflicts.len())
+        },
+        data: conflicts,
+    };
+    let json_str = serde_json::to_string(&response).expect("Failed to serialize");
+    json_str
+}
+
 #[tauri::command]
 pub async fn down() -> String {
     let mut response = Res {
@@ -60,3 +87,15 @@ pub fn change_action_type(t: u32) -> String {
     let json_str = serde_json::to_string(&response).expect("Failed to serialize");
     json_str
 }
+
+#[tauri::command]
+// 规则运行状态
+pub fn runtime_status() -> String {
+    let response: Res<RuntimeStatus> = Res {
+        code: 200,
+        message: String::from("获取成功"),
+        data: snapshot(),
+    };
+    let json_str = serde_json::to_string(&response).expect("Failed to serialize");
+    json_str
+}
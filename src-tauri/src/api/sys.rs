@@ -1,4 +1,10 @@
-use crate::user::user::{self, get_hod_on_time, update_user_config};
+use crate::memory::attach::{target, AttachState};
+use crate::memory::pointer::reload_pointers;
+use crate::memory::process::{
+    find_processes, list_processes, select_process, ProcessInfo, TargetProcess,
+};
+use crate::start::hotkey::{HotKeyConfig, HotKeys};
+use crate::user::user::{self, get_hod_on_time, get_hotkeys, update_user_config};
 use serde_json::Value;
 use tauri::{
     menu::{Menu, MenuItem, PredefinedMenuItem},
@@ -74,15 +80,110 @@ pub fn update_user_hold_on(duration: Value) -> Result<String, String> {
     }
 }
 
-// 清理进程内存
+// Tauri invoke 包装：读取全局快捷键配置
 #[tauri::command]
-pub fn clean_memory() {
+pub fn read_user_hotkeys() -> Result<HotKeyConfig, String> {
+    match get_hotkeys() {
+        Ok(hotkeys) => Ok(hotkeys),
+        Err(e) => Err(format!("读取配置失败: {}", e)),
+    }
+}
+
+// Tauri invoke 包装：更新全局快捷键配置, 下次开启规则时生效
+#[tauri::command]
+pub fn update_user_hotkeys(hotkeys: HotKeyConfig) -> Result<String, String> {
+    HotKeys::from_config(&hotkeys).map_err(|e| format!("快捷键配置有误: {}", e))?;
+    let value = serde_json::to_value(&hotkeys).map_err(|e| format!("配置更新失败: {}", e))?;
+    match update_user_config("hotkeys", value) {
+        Ok(_) => Ok("配置更新成功".to_string()),
+        Err(e) => Err(format!("配置更新失败: {}", e)),
+    }
+}
+
+// 重新读取 ~/.ruby/pointers.json 中的内存指针定义, 返回定义的数量
+#[tauri::command]
+pub fn reload_pointer_table() -> Result<usize, String> {
+    reload_pointers()
+}
+
+// 目标进程的连接状态, 之后的变化通过 process_attach 事件推送
+#[tauri::command]
+pub fn process_attach_state() -> AttachState {
+    target().state()
+}
+
+// 当前的目标进程名和选择的进程ID
+#[tauri::command]
+pub fn read_target_process() -> TargetProcess {
+    target().target_process()
+}
+
+// 更新目标进程名并保存到配置文件, 断开当前连接
+#[tauri::command]
+pub fn update_process_name(name: String) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("进程名不能为空".to_string());
+    }
+    update_user_config("process_name", Value::from(name))
+        .map_err(|e| format!("配置更新失败: {}", e))?;
+    target().set_target(TargetProcess::new(name));
+    Ok("配置更新成功".to_string())
+}
+
+// 正在运行的候选进程, name 默认为目标进程名, 为空字符串时列出所有进程
+#[tauri::command]
+pub fn list_target_processes(name: Option<String>) -> Result<Vec<ProcessInfo>, String> {
+    let name = name.unwrap_or_else(|| target().target_process().name);
+    let processes = list_processes().map_err(|e| format!("读取进程列表失败: {:?}", e))?;
+    Ok(find_processes(processes, &name))
+}
+
+// 同时打开多个客户端时选择进程ID, 为空时使用第一个匹配的进程
+#[tauri::command]
+pub fn select_target_process(pid: Option<u32>) -> Result<TargetProcess, String> {
+    let mut selected = target().target_process();
+    selected.pid = pid;
+    if let Some(pid) = pid {
+        let processes = list_processes().map_err(|e| format!("读取进程列表失败: {:?}", e))?;
+        select_process(processes, &selected)
+            .map_err(|_| format!("{} 不是正在运行的 {}", pid, selected.name))?;
+    }
+    target().set_target(selected.clone());
+    Ok(selected)
+}
+
+// 清理进程内存, 结束目标进程
+#[tauri::command]
+pub fn clean_memory() -> Result<(), String> {
+    let mut selected = target().target_process();
+    // 选择的进程已退出时进程ID可能被其他进程使用, 确认仍是目标进程才按进程ID结束
+    if selected.pid.is_some() {
+        let processes = list_processes().map_err(|e| format!("读取进程列表失败: {:?}", e))?;
+        if select_process(processes, &selected).is_err() {
+            selected.pid = None;
+            target().set_target(selected.clone());
+        }
+    }
+    // 选择了进程或已连接时只结束该进程, 没有连接时不为了读取进程ID而连接
+    let pid = selected.pid.or_else(|| target().attached_pid());
+    let pid = pid.map(|pid| pid.to_string());
     // 管理员权限执行系统命令
-    // 例如：终止某个进程
-    std::process::Command::new("taskkill")
-        .args(&["/IM", "Client.exe", "/F"])
+    #[cfg(windows)]
+    let (program, args) = match &pid {
+        Some(pid) => ("taskkill", vec!["/PID", pid.as_str(), "/F"]),
+        None => ("taskkill", vec!["/IM", selected.name.as_str(), "/F"]),
+    };
+    #[cfg(not(windows))]
+    let (program, args) = match &pid {
+        Some(pid) => ("kill", vec!["-9", pid.as_str()]),
+        None => ("pkill", vec!["-9", "-x", selected.name.as_str()]),
+    };
+    std::process::Command::new(program)
+        .args(args)
         .spawn()
-        .expect("Failed to execute command");
+        .map(|_| ())
+        .map_err(|e| format!("结束进程失败: {}", e))
 }
 
 #[tauri::command]
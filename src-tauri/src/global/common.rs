@@ -1,12 +1,20 @@
 use serde::Serialize;
-use windows::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_CXSCREEN, SM_CYSCREEN};
 
+use crate::rgb::source::screen;
+
+// 读取失败时返回 0
 pub fn screen_width() -> u32 {
-    unsafe { GetSystemMetrics(SM_CXSCREEN) as u32 }
+    screen()
+        .and_then(|s| s.size())
+        .map(|(width, _)| width)
+        .unwrap_or(0)
 }
 
 pub fn screen_height() -> u32 {
-    unsafe { GetSystemMetrics(SM_CYSCREEN) as u32 }
+    screen()
+        .and_then(|s| s.size())
+        .map(|(_, height)| height)
+        .unwrap_or(0)
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Serialize)]
@@ -21,7 +29,6 @@ impl RGB {
         RGB { r, g, b }
     }
 
-    #[allow(dead_code)]
     pub fn squared_distance_to(&self, other: &RGB) -> i32 {
         let dr = self.r as i32 - other.r as i32;
         let dg = self.g as i32 - other.g as i32;
@@ -1,32 +1,50 @@
+#[cfg(windows)]
 use lazy_static::lazy_static;
+#[cfg(windows)]
 use std::sync::Mutex;
+#[cfg(windows)]
 use windows::Win32::Foundation::HWND;
+#[cfg(windows)]
 use windows::Win32::Graphics::Gdi::{GetDC, ReleaseDC, HDC};
 
+// 只有 Windows 使用 GDI 屏幕句柄, 其他系统下开启和关闭时不需要处理
+#[cfg(windows)]
 lazy_static! {
     static ref GLOBAL_HDC_SCREEN: Mutex<Option<isize>> = Mutex::new(None);
 }
 
 pub fn init_global_hdc_screen() {
-    let mut hdc_guard = GLOBAL_HDC_SCREEN.lock().unwrap();
-    if hdc_guard.is_none() {
-        unsafe {
-            let hdc = GetDC(Some(HWND(std::ptr::null_mut())));
-            if !hdc.is_invalid() {
-                *hdc_guard = Some(hdc.0 as isize);
+    #[cfg(windows)]
+    {
+        let mut hdc_guard = GLOBAL_HDC_SCREEN.lock().unwrap();
+        if hdc_guard.is_none() {
+            unsafe {
+                let hdc = GetDC(Some(HWND(std::ptr::null_mut())));
+                if !hdc.is_invalid() {
+                    *hdc_guard = Some(hdc.0 as isize);
+                }
             }
         }
     }
 }
 
 pub fn release_global_hdc_screen() {
-    let mut hdc_guard = GLOBAL_HDC_SCREEN.lock().unwrap();
-    if let Some(hdc_raw) = *hdc_guard {
-        unsafe {
-            let hwnd = HWND(std::ptr::null_mut());
-            let hdc = HDC(hdc_raw as isize as _);
-            ReleaseDC(Some(hwnd), hdc);
+    #[cfg(windows)]
+    {
+        let mut hdc_guard = GLOBAL_HDC_SCREEN.lock().unwrap();
+        if let Some(hdc_raw) = *hdc_guard {
+            unsafe {
+                let hwnd = HWND(std::ptr::null_mut());
+                let hdc = HDC(hdc_raw as isize as _);
+                ReleaseDC(Some(hwnd), hdc);
+            }
+            *hdc_guard = None;
         }
-        *hdc_guard = None;
     }
 }
+
+/// 规则开启期间的全局屏幕句柄
+#[cfg(windows)]
+pub fn global_hdc_screen() -> Option<isize> {
+    *GLOBAL_HDC_SCREEN.lock().unwrap()
+}
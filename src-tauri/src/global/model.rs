@@ -27,6 +27,104 @@ pub struct TimeOrNama {
 pub struct Color {
     pub coordinate: String, // 鼠标坐标
     pub rgb: String,        // rgb值
+    #[serde(default)]
+    pub tolerance: f64, // 允许的偏差, 含义由 metric 决定, 默认完全一致
+    #[serde(default)]
+    pub metric: ColorMetric, // 颜色差异的计算方式
+    pub saturation: Option<f64>, // hsv 方式允许的饱和度偏差 0-1
+    #[serde(default)]
+    pub capture: CaptureMode, // 调试截图, 保存到配置目录的 captures 下
+    pub node: Option<String>, // 流程图中的节点id, 区分不同节点的调试截图
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Serialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureMode {
+    #[default]
+    Off,
+    Always, // 每次判断都保存, 间隔不少于配置的 interval
+    Flip,   // 判断结果变化时保存
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Serialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorMetric {
+    #[default]
+    ChannelMax, // 单个通道的最大差值 0-255
+    Euclidean, // rgb 空间的直线距离 0-441
+    Hsv,       // 色相差值 0-180 度, 忽略亮度, 饱和度差值由 saturation 限制
+    Ciede2000, // CIEDE2000 色差, 1 左右人眼难以分辨
+}
+
+#[derive(Debug, Deserialize, Clone, Serialize, Default)]
+pub struct PatternPoint {
+    pub dx: i32,     // 相对锚点的横向偏移
+    pub dy: i32,     // 相对锚点的纵向偏移
+    pub rgb: String, // rgb值
+    #[serde(default)]
+    pub tolerance: i32, // 允许的偏差
+}
+
+#[derive(Debug, Deserialize, Clone, Serialize, Default)]
+pub struct ColorPattern {
+    pub anchor: Option<String>,    // 锚点坐标, 不搜索时使用
+    pub points: Vec<PatternPoint>, // 相对锚点的取色点
+    pub min_match: Option<usize>,  // 至少匹配的点数, 为空时需要全部匹配
+    pub search: Option<String>,    // 搜索区域 "x,y,宽,高", 设置后在区域内查找锚点位置
+}
+
+#[derive(Debug, Deserialize, Clone, Serialize, Default)]
+pub struct Template {
+    pub template: String,       // 模板图片名称, 保存在配置目录的 templates 下
+    pub region: String,         // 搜索区域 "x,y,宽,高"
+    pub threshold: Option<f64>, // 相似度阈值 0-1, 默认0.9
+    #[serde(default)]
+    pub store: bool, // 记录找到的位置(模板中心), 供后面的鼠标操作使用
+}
+
+#[derive(Debug, Deserialize, Clone, Serialize, Default)]
+pub struct Bar {
+    pub bar: String, // 血条/蓝条区域 "x,y,宽,高"
+    pub rgb: String, // 填充部分的颜色
+    #[serde(default)]
+    pub tolerance: f64, // 允许的偏差, 含义由 metric 决定
+    #[serde(default)]
+    pub metric: ColorMetric, // 颜色差异的计算方式
+    pub saturation: Option<f64>, // hsv 方式允许的饱和度偏差 0-1
+    #[serde(default)]
+    pub direction: BarDirection, // 填充方向
+    pub below: Option<f64>, // 填充百分比低于该值时成立 0-100
+    pub above: Option<f64>, // 填充百分比高于该值时成立 0-100
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Serialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BarDirection {
+    #[default]
+    LeftToRight, // 从左往右填充, 减少时右边先变空
+    RightToLeft, // 从右往左填充
+}
+
+#[derive(Debug, Deserialize, Clone, Serialize, Default)]
+pub struct Digits {
+    pub digits: String, // 字形集名称, 保存在配置目录的 digits 下
+    pub region: String, // 数字所在区域 "x,y,宽,高"
+    pub rgb: String,    // 数字的颜色
+    #[serde(default)]
+    pub tolerance: f64, // 允许的偏差, 含义由 metric 决定
+    #[serde(default)]
+    pub metric: ColorMetric, // 颜色差异的计算方式
+    pub saturation: Option<f64>, // hsv 方式允许的饱和度偏差 0-1
+    pub min_score: Option<f64>, // 字形相似度阈值 0-1, 默认0.8
+    pub below: Option<i64>, // 数字小于该值时成立
+    pub above: Option<i64>, // 数字大于该值时成立
+    pub equal: Option<i64>, // 数字等于该值时成立
+}
+
+#[derive(Debug, Deserialize, Clone, Serialize, Default)]
+pub struct Mouse {
+    pub mouse: String,            // move: 移动 left: 左键单击 right: 右键单击
+    pub position: Option<String>, // 坐标 "x,y", 为空时使用最近找到的位置
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -36,6 +134,11 @@ pub enum ElementEnum {
     Skill(Skill),
     TimeOrNama(TimeOrNama),
     Color(Color),
+    ColorPattern(ColorPattern),
+    Template(Template),
+    Bar(Bar),
+    Digits(Digits),
+    Mouse(Mouse),
 }
 
 impl Default for ElementEnum {
@@ -51,9 +154,93 @@ pub struct Children {
     pub children: Option<Vec<Children>>, // 子元素
 }
 
+#[derive(Debug, Deserialize, Clone, Serialize, Default)]
+pub struct Timer {
+    pub interval: Option<u64>, // 执行间隔ms, 为空时只执行一次
+    pub delay: Option<u64>,    // 开启后第一次执行的延迟ms, 为空时等于执行间隔
+}
+
+#[derive(Debug, Deserialize, Clone, Serialize)]
+#[serde(tag = "t", rename_all = "snake_case")]
+pub enum WatchCondition {
+    // 像素颜色匹配, mismatch 为 true 时颜色不匹配才算成立
+    Pixel {
+        coordinate: String,
+        rgb: String,
+        #[serde(default)]
+        deviation: i32,
+        #[serde(default)]
+        mismatch: bool,
+    },
+    // 内力不高于 below, 成立后高于 release 才结束
+    Mana {
+        below: u32,
+        release: Option<u32>,
+    },
+}
+
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct Watch {
+    pub condition: WatchCondition, // 检测的条件
+    #[serde(default)]
+    pub edge: bool, // true: 条件成立时执行一次 false: 条件成立期间循环执行
+    pub confirm: Option<u32>,      // 连续多少次检测结果一致才切换状态, 默认1
+    pub rate: Option<u64>,         // 检测间隔ms
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerMode {
+    Hold, // 按住时循环执行, 抬起停止
+    #[serde(alias = "click")]
+    Toggle, // 按一次开始循环执行, 再按一次停止
+    Once, // 每次按下执行一遍
+    DoubleTap, // 双击时执行一遍
+    TapHold, // 轻按执行轻按流程, 长按循环执行主流程
+}
+
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct Trigger {
+    pub code: u32,                 // 触发码, 键盘或鼠标
+    pub mode: Option<TriggerMode>, // 触发模式, 为空时使用全局操作方式
+    pub auto_off: Option<u64>,     // 切换模式下开启后自动停止的时间s
+    pub window: Option<u64>,       // 双击模式下两次按下的最大间隔ms
+    #[serde(default)]
+    pub suppress: bool, // 拦截触发键, 不再传给游戏
+}
+
 #[derive(Debug, Deserialize, Clone, Serialize, Default)]
 pub struct Elements {
     // pub woke_type: u32,  // 操作方式 1: 长按 2: 单击
     pub header: Element, // 操作第一个元素
     pub children: Option<Vec<Children>>,
+    pub tap_children: Option<Vec<Children>>, // 轻按时执行的流程, 设置后按长按时间区分轻按与长按
+    pub timer: Option<Timer>,                // 定时触发, 设置后不再监听触发键
+    pub watch: Option<Watch>,                // 条件触发, 设置后不再监听触发键
+    pub triggers: Option<Vec<Trigger>>,      // 多个触发键, 设置后不再使用 header 的按键
+    pub frame_age: Option<u64>,              // 取色截图最长复用时间ms, 为空时每轮执行截图一次
+    pub coordinates: Option<CoordinateSpace>, // 坐标的记录方式, 为空时为虚拟桌面的绝对坐标
+}
+
+#[derive(Debug, Deserialize, Clone, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CoordinateSpace {
+    Screen, // 虚拟桌面的绝对坐标, 主显示器左上角为 (0, 0)
+    // 按参考分辨率记录, 开启时按当前分辨率缩放
+    Reference {
+        width: u32,
+        height: u32,
+    },
+    // 相对显示器左上角, monitor 为 primary、编号或系统中的名称, 记录了显示器大小时按当前大小缩放
+    Monitor {
+        monitor: String,
+        width: Option<u32>,
+        height: Option<u32>,
+    },
+    // 相对窗口客户区左上角, 记录了客户区大小时按当前大小缩放
+    Window {
+        title: String,
+        width: Option<u32>,
+        height: Option<u32>,
+    },
 }
@@ -0,0 +1,181 @@
+use rdev::{Button, Key};
+
+// 触发码约定:
+// 1..=7 保留给鼠标, 其中 4/5 沿用原来的侧下键/侧上键编码
+// 其余为键盘按键, 使用 Windows 虚拟键码(VK)
+pub const MOUSE_LEFT: u32 = 1; // 左键
+pub const MOUSE_RIGHT: u32 = 2; // 右键
+pub const MOUSE_MIDDLE: u32 = 3; // 中键
+pub const MOUSE_X1: u32 = 4; // 侧下键
+pub const MOUSE_X2: u32 = 5; // 侧上键
+pub const MOUSE_WHEEL_UP: u32 = 6; // 滚轮上滚
+pub const MOUSE_WHEEL_DOWN: u32 = 7; // 滚轮下滚
+
+/// 支持的鼠标触发码
+pub const MOUSE_CODES: [(u32, &str); 7] = [
+    (MOUSE_LEFT, "左键"),
+    (MOUSE_RIGHT, "右键"),
+    (MOUSE_MIDDLE, "中键"),
+    (MOUSE_X1, "侧下键"),
+    (MOUSE_X2, "侧上键"),
+    (MOUSE_WHEEL_UP, "滚轮上"),
+    (MOUSE_WHEEL_DOWN, "滚轮下"),
+];
+
+/// 支持的键盘触发码 (按键, 虚拟键码, 名称)
+pub const KEY_CODES: &[(Key, u32, &str)] = &[
+    (Key::KeyA, 65, "A"),
+    (Key::KeyB, 66, "B"),
+    (Key::KeyC, 67, "C"),
+    (Key::KeyD, 68, "D"),
+    (Key::KeyE, 69, "E"),
+    (Key::KeyF, 70, "F"),
+    (Key::KeyG, 71, "G"),
+    (Key::KeyH, 72, "H"),
+    (Key::KeyI, 73, "I"),
+    (Key::KeyJ, 74, "J"),
+    (Key::KeyK, 75, "K"),
+    (Key::KeyL, 76, "L"),
+    (Key::KeyM, 77, "M"),
+    (Key::KeyN, 78, "N"),
+    (Key::KeyO, 79, "O"),
+    (Key::KeyP, 80, "P"),
+    (Key::KeyQ, 81, "Q"),
+    (Key::KeyR, 82, "R"),
+    (Key::KeyS, 83, "S"),
+    (Key::KeyT, 84, "T"),
+    (Key::KeyU, 85, "U"),
+    (Key::KeyV, 86, "V"),
+    (Key::KeyW, 87, "W"),
+    (Key::KeyX, 88, "X"),
+    (Key::KeyY, 89, "Y"),
+    (Key::KeyZ, 90, "Z"),
+    (Key::Num0, 48, "0"),
+    (Key::Num1, 49, "1"),
+    (Key::Num2, 50, "2"),
+    (Key::Num3, 51, "3"),
+    (Key::Num4, 52, "4"),
+    (Key::Num5, 53, "5"),
+    (Key::Num6, 54, "6"),
+    (Key::Num7, 55, "7"),
+    (Key::Num8, 56, "8"),
+    (Key::Num9, 57, "9"),
+    (Key::Space, 32, "Space"),
+    (Key::Return, 13, "Enter"),
+    (Key::Escape, 27, "Esc"),
+    (Key::Tab, 9, "Tab"),
+    (Key::Backspace, 8, "Backspace"),
+    (Key::ShiftLeft, 16, "Shift"),
+    (Key::ShiftRight, 16, "Shift"),
+    (Key::ControlLeft, 17, "Ctrl"),
+    (Key::ControlRight, 17, "Ctrl"),
+    (Key::Alt, 18, "Alt"),
+    (Key::AltGr, 18, "Alt"),
+    (Key::PageUp, 33, "PageUp"),
+    (Key::PageDown, 34, "PageDown"),
+    (Key::End, 35, "End"),
+    (Key::Home, 36, "Home"),
+    (Key::LeftArrow, 37, "Left"),
+    (Key::UpArrow, 38, "Up"),
+    (Key::RightArrow, 39, "Right"),
+    (Key::DownArrow, 40, "Down"),
+    (Key::Insert, 45, "Insert"),
+    (Key::Delete, 46, "Delete"),
+    (Key::F1, 112, "F1"),
+    (Key::F2, 113, "F2"),
+    (Key::F3, 114, "F3"),
+    (Key::F4, 115, "F4"),
+    (Key::F5, 116, "F5"),
+    (Key::F6, 117, "F6"),
+    (Key::F7, 118, "F7"),
+    (Key::F8, 119, "F8"),
+    (Key::F9, 120, "F9"),
+    (Key::F10, 121, "F10"),
+    (Key::F11, 122, "F11"),
+    (Key::F12, 123, "F12"),
+    (Key::CapsLock, 20, "CapsLock"),
+    (Key::NumLock, 144, "NumLock"),
+];
+
+// 将按键转换为代码, 未知按键返回0
+pub fn key_to_code(key: Key) -> u32 {
+    KEY_CODES
+        .iter()
+        .find(|(k, _, _)| *k == key)
+        .map(|(_, code, _)| *code)
+        .unwrap_or(0)
+}
+
+// 将鼠标按键转换为代码, 不支持的按键返回None
+pub fn button_to_code(button: Button) -> Option<u32> {
+    match button {
+        Button::Left => Some(MOUSE_LEFT),
+        Button::Right => Some(MOUSE_RIGHT),
+        Button::Middle => Some(MOUSE_MIDDLE),
+        // Windows 下侧键为 XBUTTON1/XBUTTON2, X11 下为 8/9 号键
+        Button::Unknown(1) | Button::Unknown(8) => Some(MOUSE_X1),
+        Button::Unknown(2) | Button::Unknown(9) => Some(MOUSE_X2),
+        Button::Unknown(_) => None,
+    }
+}
+
+// 将滚轮方向转换为代码, 横向滚动不支持
+pub fn wheel_to_code(delta_y: i64) -> Option<u32> {
+    if delta_y > 0 {
+        Some(MOUSE_WHEEL_UP)
+    } else if delta_y < 0 {
+        Some(MOUSE_WHEEL_DOWN)
+    } else {
+        None
+    }
+}
+
+pub fn is_mouse_code(code: u32) -> bool {
+    MOUSE_CODES.iter().any(|(c, _)| *c == code)
+}
+
+// 滚轮没有抬起的过程, 按下后立即视为抬起
+pub fn is_wheel_code(code: u32) -> bool {
+    code == MOUSE_WHEEL_UP || code == MOUSE_WHEEL_DOWN
+}
+
+/// 触发码是否可以被监听到
+pub fn is_supported(code: u32) -> bool {
+    is_mouse_code(code) || KEY_CODES.iter().any(|(_, c, _)| *c == code)
+}
+
+// 组合键中可以使用的修饰键, 按 Ctrl/Shift/Alt 的顺序显示
+pub const MODIFIER_CODES: [u32; 3] = [17, 16, 18];
+
+pub fn is_modifier_code(code: u32) -> bool {
+    MODIFIER_CODES.contains(&code)
+}
+
+/// 名称对应的触发码, 忽略大小写
+pub fn name_to_code(name: &str) -> Option<u32> {
+    let name = name.trim();
+    MOUSE_CODES
+        .iter()
+        .find(|(_, n)| *n == name)
+        .map(|(c, _)| *c)
+        .or_else(|| {
+            KEY_CODES
+                .iter()
+                .find(|(_, _, n)| n.eq_ignore_ascii_case(name))
+                .map(|(_, c, _)| *c)
+        })
+}
+
+/// 触发码对应的名称
+pub fn code_name(code: u32) -> Option<&'static str> {
+    MOUSE_CODES
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, name)| *name)
+        .or_else(|| {
+            KEY_CODES
+                .iter()
+                .find(|(_, c, _)| *c == code)
+                .map(|(_, _, name)| *name)
+        })
+}
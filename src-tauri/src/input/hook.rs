@@ -0,0 +1,268 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{channel, Receiver, RecvTimeoutError, Sender},
+        Mutex, Once,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+#[cfg(windows)]
+use rdev::grab;
+use rdev::{listen, Button, Event, EventType, Key};
+
+use crate::{
+    global::global::STOP_ACTION,
+    input::code::{button_to_code, is_wheel_code, key_to_code, wheel_to_code},
+};
+
+/// 键盘/鼠标输入事件, 按键重复按下的事件已被过滤
+#[derive(Debug, Clone, Copy)]
+pub struct InputEvent {
+    pub code: u32,     // 触发码
+    pub pressed: bool, // true: 按下 false: 抬起
+    pub time: Instant, // 事件时间
+}
+
+lazy_static! {
+    static ref SUBSCRIBERS: Mutex<Vec<(u64, Sender<InputEvent>)>> = Mutex::new(Vec::new()); // 事件订阅者
+    static ref HELD: Mutex<HashMap<u32, HashSet<Physical>>> = Mutex::new(HashMap::new()); // 当前按住的按键, 按触发码分组
+    static ref SUPPRESSED: Mutex<HashSet<u32>> = Mutex::new(HashSet::new()); // 需要拦截的触发键
+    static ref CURSOR: Mutex<Option<(i32, i32)>> = Mutex::new(None); // 最近一次鼠标移动的位置
+    static ref INJECTED: Mutex<Vec<(u32, bool, Instant)>> = Mutex::new(Vec::new()); // 程序自己发送、钩子还没收到的输入
+}
+
+// 程序发送的输入在这个时间内没有经过钩子时不再等待 (例如钩子还没安装)
+const INJECTED_TTL: Duration = Duration::from_secs(1);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+static HOOK: Once = Once::new();
+static GRABBING: AtomicBool = AtomicBool::new(false); // 钩子是否能拦截按键
+
+// 实际按下的按键, 左右两侧的修饰键触发码相同, 需要分别记录
+#[derive(PartialEq, Eq, Hash)]
+enum Physical {
+    Key(Key),
+    Button(Button),
+}
+
+/// 输入事件订阅, 离开作用域时自动取消订阅
+pub struct Subscription {
+    id: u64,
+    rx: Receiver<InputEvent>,
+}
+
+impl Subscription {
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<InputEvent, RecvTimeoutError> {
+        self.rx.recv_timeout(timeout)
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        SUBSCRIBERS.lock().unwrap().retain(|(id, _)| *id != self.id);
+    }
+}
+
+/**
+ * 订阅全局输入事件
+ * 所有规则共用同一个系统钩子, 第一次订阅时安装
+ */
+pub fn subscribe() -> Subscription {
+    ensure_hook();
+    add_subscriber()
+}
+
+/// 只登记订阅者, 不安装系统钩子, 事件由调用 dispatch 的一方提供
+pub fn add_subscriber() -> Subscription {
+    let (tx, rx) = channel();
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    SUBSCRIBERS.lock().unwrap().push((id, tx));
+    Subscription { id, rx }
+}
+
+/// 按键当前是否处于按下状态
+pub fn is_held(code: u32) -> bool {
+    HELD.lock().unwrap().contains_key(&code)
+}
+
+/// 钩子记录的鼠标位置, 安装钩子后鼠标还没有移动过时为 None
+pub fn cursor_position() -> Option<(i32, i32)> {
+    *CURSOR.lock().unwrap()
+}
+
+/// 当前是否能拦截按键, 不能拦截时触发键仍会传给游戏
+pub fn can_suppress() -> bool {
+    GRABBING.load(Ordering::Relaxed)
+}
+
+/**
+ * 设置需要拦截的触发键, 拦截的按键只触发规则, 不会再传给游戏
+ * 暂停期间不拦截, 传入空集合取消拦截
+ */
+pub fn set_suppressed(codes: HashSet<u32>) {
+    if !codes.is_empty() {
+        ensure_hook();
+        if !can_suppress() {
+            eprintln!("当前系统不支持拦截按键, 触发键仍会传给游戏");
+        }
+    }
+    replace_suppressed(codes);
+}
+
+/// 只更换需要拦截的触发键, 不安装系统钩子
+pub fn replace_suppressed(codes: HashSet<u32>) {
+    *SUPPRESSED.lock().unwrap() = codes;
+}
+
+/**
+ * 程序自己模拟输入之前调用, 钩子收到这个事件时直接放行
+ * 不拦截、不记录按住状态、也不触发规则
+ * rdev 的事件中没有 LLKHF_INJECTED 标记, 因此在发送前记录, 收到时按触发码和按下/抬起匹配
+ */
+#[cfg_attr(not(windows), allow(dead_code))]
+pub fn mark_injected(code: u32, pressed: bool) {
+    let now = Instant::now();
+    let mut injected = INJECTED.lock().unwrap();
+    injected.retain(|(_, _, time)| now.duration_since(*time) < INJECTED_TTL);
+    injected.push((code, pressed, now));
+}
+
+// 事件是否由程序自己发送, 匹配到时移除记录
+fn take_injected(code: u32, pressed: bool) -> bool {
+    let now = Instant::now();
+    let mut injected = INJECTED.lock().unwrap();
+    injected.retain(|(_, _, time)| now.duration_since(*time) < INJECTED_TTL);
+    match injected
+        .iter()
+        .position(|(c, p, _)| *c == code && *p == pressed)
+    {
+        Some(index) => {
+            injected.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+// rdev 在 Windows 下只保存一个回调, 多次 listen 会互相覆盖, 因此只安装一次
+// 支持 grab 时使用 grab 安装钩子以便拦截按键, 安装失败时退回只监听
+fn ensure_hook() {
+    HOOK.call_once(|| {
+        #[cfg(windows)]
+        GRABBING.store(true, Ordering::Relaxed);
+        thread::spawn(|| {
+            #[cfg(windows)]
+            {
+                let result = grab(|event| if dispatch(&event) { None } else { Some(event) });
+                match result {
+                    Ok(()) => return,
+                    Err(error) => {
+                        GRABBING.store(false, Ordering::Relaxed);
+                        eprintln!("按键拦截不可用, 改为只监听: {:?}", error);
+                    }
+                }
+            }
+            if let Err(error) = listen(|event| {
+                dispatch(&event);
+            }) {
+                eprintln!("输入监听错误: {:?}", error);
+            }
+        });
+    });
+}
+
+/// 分发输入事件, 返回 true 表示该事件需要被拦截
+pub fn dispatch(event: &Event) -> bool {
+    let paused = *STOP_ACTION.lock().unwrap();
+    dispatch_with(event, paused)
+}
+
+/// 按给定的暂停状态分发输入事件, 暂停期间不拦截
+pub fn dispatch_with(event: &Event, paused: bool) -> bool {
+    let (code, pressed) = match event.event_type {
+        EventType::KeyPress(key) => (key_to_code(key), true),
+        EventType::KeyRelease(key) => (key_to_code(key), false),
+        EventType::ButtonPress(button) => match button_to_code(button) {
+            Some(code) => (code, true),
+            None => return false,
+        },
+        EventType::ButtonRelease(button) => match button_to_code(button) {
+            Some(code) => (code, false),
+            None => return false,
+        },
+        EventType::Wheel { delta_y, .. } => match wheel_to_code(delta_y) {
+            Some(code) => (code, true),
+            None => return false,
+        },
+        EventType::MouseMove { x, y } => {
+            *CURSOR.lock().unwrap() = Some((x.round() as i32, y.round() as i32));
+            return false;
+        }
+    };
+    if code == 0 {
+        return false;
+    }
+    // 程序自己发送的按键不能被拦截, 也不能改变按住状态或再次触发规则
+    if take_injected(code, pressed) {
+        return false;
+    }
+    // 按住时的重复按下事件也要拦截, 所以在过滤之前判断
+    let suppress = SUPPRESSED.lock().unwrap().contains(&code) && !paused;
+
+    let now = Instant::now();
+    if is_wheel_code(code) {
+        // 滚轮按下后立即抬起
+        publish(InputEvent {
+            code,
+            pressed: true,
+            time: now,
+        });
+        publish(InputEvent {
+            code,
+            pressed: false,
+            time: now,
+        });
+        return suppress;
+    }
+
+    let physical = match event.event_type {
+        EventType::KeyPress(key) | EventType::KeyRelease(key) => Physical::Key(key),
+        EventType::ButtonPress(button) | EventType::ButtonRelease(button) => {
+            Physical::Button(button)
+        }
+        _ => return suppress,
+    };
+    // 过滤长按时系统产生的重复按下事件, 同一触发码的按键全部松开时才算抬起
+    let changed = {
+        let mut held = HELD.lock().unwrap();
+        if pressed {
+            let keys = held.entry(code).or_default();
+            keys.insert(physical) && keys.len() == 1
+        } else {
+            let released = held
+                .get_mut(&code)
+                .is_some_and(|keys| keys.remove(&physical) && keys.is_empty());
+            if released {
+                held.remove(&code);
+            }
+            released
+        }
+    };
+    if changed {
+        publish(InputEvent {
+            code,
+            pressed,
+            time: now,
+        });
+    }
+    suppress
+}
+
+fn publish(event: InputEvent) {
+    let mut subscribers = SUBSCRIBERS.lock().unwrap();
+    subscribers.retain(|(_, tx)| tx.send(event).is_ok());
+}
@@ -1,6 +1,7 @@
 mod act;
 mod api;
 mod global;
+mod input;
 mod memory;
 mod rgb;
 mod start;
@@ -13,6 +14,8 @@ use tauri::Manager;
 use crate::api::color;
 use crate::api::sys;
 use crate::global::global::STOP_ACTION;
+use crate::memory::attach::{target, ALIVE_INTERVAL};
+use crate::rgb::probe::unwatch_all;
 use crate::user::user::create_user_config_dir;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -41,12 +44,31 @@ pub fn run() {
             sys::get_loacl_mac_md5, // 获取本地mac地址的md5值
             // sys::create_tray,               // 创建托盘
             color::get_mouse_rgb,           // 鼠标位置的rgb值
+            color::get_mouse_rgb_samples,   // 采样取色并建议偏差
+            color::pick_color,              // 取色并推送鼠标位置和颜色
+            color::cancel_color_picker,     // 取消取色
+            color::save_screen_template,    // 截取屏幕区域保存为模板
+            color::save_digit_glyphs,       // 截取屏幕区域保存数字字形
+            color::read_screen_digits,      // 识别屏幕区域内的数字
+            color::list_monitors,           // 显示器布局
+            color::watch_pixel_colors,      // 订阅坐标颜色
+            color::unwatch_pixel_colors,    // 取消订阅坐标颜色
             api::start::run,                // 开始执行
             api::start::down,               // 停止执行
             api::start::change_action_type, // 改变操作方式
+            api::start::runtime_status,     // 规则运行状态
+            api::start::check_conflicts,    // 检查触发键冲突
             sys::create_tray,               // 创建托盘
             sys::update_user_hold_on,       // 更新用户配置
             sys::read_user_hold_on,         // 读取用户配置中的长按时间
+            sys::read_user_hotkeys,         // 读取全局快捷键配置
+            sys::update_user_hotkeys,       // 更新全局快捷键配置
+            sys::reload_pointer_table,      // 重新读取内存指针定义
+            sys::process_attach_state,      // 目标进程的连接状态
+            sys::read_target_process,       // 读取目标进程
+            sys::update_process_name,       // 更新目标进程名
+            sys::list_target_processes,     // 正在运行的候选进程
+            sys::select_target_process,     // 选择目标进程ID
             sys::clean_memory,              // 清理进程内存
             sys::close_app,                 // 关闭应用
         ])
@@ -58,6 +80,17 @@ pub fn run() {
                 eprintln!("Failed to create tray: {}", e);
             }
 
+            // 目标进程连接状态变化时推送给界面
+            let attach_handle = app.handle().clone();
+            target().on_change(move |state| {
+                let _ = attach_handle.emit("process_attach", state);
+            });
+            // 没有规则运行时也定时检查目标进程是否退出
+            std::thread::spawn(|| loop {
+                std::thread::sleep(ALIVE_INTERVAL);
+                target().check_alive();
+            });
+
             let app_handle: tauri::AppHandle = app.handle().clone();
             std::thread::spawn(move || loop {
                 let stop_action = {
@@ -75,6 +108,8 @@ pub fn run() {
         })
         .on_window_event(|window, event| {
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                // 窗口隐藏后不再需要推送坐标颜色
+                unwatch_all();
                 // 阻止窗口关闭，改为隐藏
                 let _ = window.hide();
                 api.prevent_close();
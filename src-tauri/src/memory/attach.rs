@@ -0,0 +1,238 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+use serde::Serialize;
+
+use crate::{
+    memory::process::{open_process, MemoryError, ProcessMemory, TargetProcess},
+    user::user::get_process_name,
+};
+
+/// 连接失败后重新连接的间隔
+pub const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+/// 后台检查目标进程是否退出的间隔
+pub const ALIVE_INTERVAL: Duration = Duration::from_secs(2);
+
+type Opener =
+    Box<dyn Fn(&TargetProcess, usize) -> Result<Box<dyn ProcessMemory>, MemoryError> + Send + Sync>;
+type Listener = Box<dyn Fn(&AttachState) + Send + Sync>;
+
+lazy_static! {
+    static ref TARGET: Attachment = Attachment::new(
+        TargetProcess::new(&get_process_name()),
+        0x00400000,
+        RETRY_INTERVAL,
+        open_process
+    ); // 所有规则共用的连接
+}
+
+/// 连接状态, 变化时通过 process_attach 事件推送给界面
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum AttachState {
+    Detached,                 // 还没有连接
+    Attached { pid: u32 },    // 已连接
+    NotFound,                 // 进程没有运行
+    Failed { error: String }, // 打开进程失败, 例如权限不足
+}
+
+struct Inner {
+    target: TargetProcess,
+    process: Option<Arc<dyn ProcessMemory>>,
+    state: AttachState,
+    last_try: Option<Instant>, // 上次尝试连接的时间
+}
+
+/**
+ * 对目标进程的长期连接, 第一次使用时打开进程, 之后一直复用
+ * 进程退出或重启后自动重新连接, 连接失败时间隔 retry 才再次尝试
+ */
+pub struct Attachment {
+    base_address: usize,
+    retry: Duration,
+    open: Opener,
+    inner: Mutex<Inner>,
+    connecting: Mutex<()>, // 打开进程期间持有
+    listener: Mutex<Option<Listener>>,
+}
+
+impl Attachment {
+    pub fn new(
+        target: TargetProcess,
+        base_address: usize,
+        retry: Duration,
+        open: impl Fn(&TargetProcess, usize) -> Result<Box<dyn ProcessMemory>, MemoryError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Attachment {
+            base_address,
+            retry,
+            open: Box::new(open),
+            inner: Mutex::new(Inner {
+                target,
+                process: None,
+                state: AttachState::Detached,
+                last_try: None,
+            }),
+            connecting: Mutex::new(()),
+            listener: Mutex::new(None),
+        }
+    }
+
+    /// 设置连接状态变化时的回调
+    pub fn on_change(&self, listener: impl Fn(&AttachState) + Send + Sync + 'static) {
+        *self.listener.lock().unwrap() = Some(Box::new(listener));
+    }
+
+    pub fn state(&self) -> AttachState {
+        self.inner.lock().unwrap().state.clone()
+    }
+
+    pub fn target_process(&self) -> TargetProcess {
+        self.inner.lock().unwrap().target.clone()
+    }
+
+    /// 更换目标进程, 断开当前连接, 下次使用时连接新的目标
+    pub fn set_target(&self, target: TargetProcess) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.target = target;
+        inner.process = None;
+        inner.last_try = None;
+        let changed = inner.state != AttachState::Detached;
+        inner.state = AttachState::Detached;
+        drop(inner);
+        if changed {
+            self.notify(&AttachState::Detached);
+        }
+    }
+
+    /// 已连接且还在运行的进程ID, 不会触发连接
+    pub fn attached_pid(&self) -> Option<u32> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .process
+            .as_ref()
+            .filter(|process| process.is_alive())
+            .map(|process| process.pid())
+    }
+
+    fn notify(&self, state: &AttachState) {
+        if let Some(listener) = self.listener.lock().unwrap().as_ref() {
+            listener(state);
+        }
+    }
+
+    /// 当前连接的进程, 没有连接或进程已退出时重新连接
+    pub fn process(&self) -> Result<Arc<dyn ProcessMemory>, MemoryError> {
+        self.process_at(Instant::now())
+    }
+
+    pub fn process_at(&self, now: Instant) -> Result<Arc<dyn ProcessMemory>, MemoryError> {
+        // 同一时间只有一个调用者打开进程, 其他调用者等待后复用连接
+        let _connecting = self.connecting.lock().unwrap();
+        let mut inner = self.inner.lock().unwrap();
+        let previous = inner.state.clone();
+        let result = match self.prepare(&mut inner, now) {
+            Some(result) => result,
+            None => {
+                // 查找和打开进程可能较慢, 期间不占用连接状态, 界面可以读取状态或更换目标
+                let target = inner.target.clone();
+                drop(inner);
+                let opened = (self.open)(&target, self.base_address);
+                inner = self.inner.lock().unwrap();
+                if inner.target == target {
+                    // 选择的进程ID已经没有在运行, 改为连接同名的进程, 界面通过状态变化重新读取目标
+                    if inner.target.pid.is_some()
+                        && matches!(opened, Err(MemoryError::ProcessNotFound))
+                    {
+                        inner.target.pid = None;
+                        inner.last_try = None;
+                    }
+                    Self::finish(&mut inner, opened)
+                } else {
+                    // 打开期间更换了目标, 丢弃结果, 下次使用时连接新的目标
+                    Err(MemoryError::ProcessNotFound)
+                }
+            }
+        };
+        let state = inner.state.clone();
+        drop(inner);
+        // 回调在释放锁之后执行, 回调中可以读取状态
+        if state != previous {
+            self.notify(&state);
+        }
+        result
+    }
+
+    /// 已连接的进程退出时重新连接, 没有规则使用连接时也能及时推送状态
+    pub fn check_alive(&self) {
+        let process = self.inner.lock().unwrap().process.clone();
+        if process.is_some_and(|process| !process.is_alive()) {
+            let _ = self.process();
+        }
+    }
+
+    // 复用已有的连接, 或在重试间隔内返回上次的错误, 需要重新打开进程时返回 None
+    fn prepare(
+        &self,
+        inner: &mut Inner,
+        now: Instant,
+    ) -> Option<Result<Arc<dyn ProcessMemory>, MemoryError>> {
+        if let Some(process) = inner.process.as_ref() {
+            if process.is_alive() {
+                return Some(Ok(process.clone()));
+            }
+            // 进程已退出, 选择的进程ID不再有效, 立即尝试连接重启后的进程
+            if inner.target.pid == Some(process.pid()) {
+                inner.target.pid = None;
+            }
+            inner.process = None;
+            inner.state = AttachState::Detached;
+            inner.last_try = None;
+        }
+        if let Some(last_try) = inner.last_try {
+            if now.saturating_duration_since(last_try) < self.retry {
+                return Some(Err(match &inner.state {
+                    AttachState::Failed { error } => MemoryError::OpenProcessFailed(error.clone()),
+                    _ => MemoryError::ProcessNotFound,
+                }));
+            }
+        }
+        inner.last_try = Some(now);
+        None
+    }
+
+    // 记录打开进程的结果
+    fn finish(
+        inner: &mut Inner,
+        opened: Result<Box<dyn ProcessMemory>, MemoryError>,
+    ) -> Result<Arc<dyn ProcessMemory>, MemoryError> {
+        match opened {
+            Ok(process) => {
+                let process: Arc<dyn ProcessMemory> = Arc::from(process);
+                inner.state = AttachState::Attached { pid: process.pid() };
+                inner.process = Some(process.clone());
+                Ok(process)
+            }
+            Err(e) => {
+                inner.state = match &e {
+                    MemoryError::ProcessNotFound => AttachState::NotFound,
+                    e => AttachState::Failed {
+                        error: format!("{:?}", e),
+                    },
+                };
+                Err(e)
+            }
+        }
+    }
+}
+
+/// 所有规则共用的目标进程连接
+pub fn target() -> &'static Attachment {
+    &TARGET
+}
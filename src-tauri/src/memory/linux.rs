@@ -0,0 +1,172 @@
+use std::{fs::File, os::unix::fs::FileExt};
+
+use crate::memory::process::{MemoryError, ProcessInfo, ProcessMemory};
+
+/**
+ * Linux 进程, 使用 process_vm_readv 读取内存
+ * 系统不允许 process_vm_readv 时改为读取 /proc/<pid>/mem
+ * 需要有 ptrace 目标进程的权限 (同一用户且 ptrace_scope 允许, 或者是目标进程的父进程)
+ */
+pub struct LinuxProcess {
+    pid: u32,
+    base_address: usize,
+    start_time: Option<u64>, // 进程启动时间, 用来区分重启后使用相同进程ID的进程
+}
+
+impl LinuxProcess {
+    pub fn attach(pid: u32, base_address: usize) -> Self {
+        LinuxProcess {
+            pid,
+            base_address,
+            start_time: start_time(pid),
+        }
+    }
+
+    // 读取 /proc/<pid>/mem, 读到的字节可能少于 buffer
+    fn read_proc_mem(&self, address: usize, buffer: &mut [u8]) -> Result<usize, MemoryError> {
+        let file = File::open(format!("/proc/{}/mem", self.pid))
+            .map_err(|e| MemoryError::OpenProcessFailed(e.to_string()))?;
+        let mut read = 0;
+        while read < buffer.len() {
+            match file.read_at(&mut buffer[read..], (address + read) as u64) {
+                Ok(0) => break,
+                Ok(n) => read += n,
+                Err(_) if read > 0 => break,
+                Err(e) => {
+                    return Err(MemoryError::ReadMemoryFailed(format!(
+                        "{:#X}: {}",
+                        address, e
+                    )))
+                }
+            }
+        }
+        Ok(read)
+    }
+}
+
+impl ProcessMemory for LinuxProcess {
+    fn read_bytes(&self, address: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
+        let mut buffer = vec![0u8; size];
+        let local = libc::iovec {
+            iov_base: buffer.as_mut_ptr() as *mut libc::c_void,
+            iov_len: size,
+        };
+        let remote = libc::iovec {
+            iov_base: address as *mut libc::c_void,
+            iov_len: size,
+        };
+        let result =
+            unsafe { libc::process_vm_readv(self.pid as libc::pid_t, &local, 1, &remote, 1, 0) };
+        let read = if result >= 0 {
+            result as usize
+        } else {
+            let error = std::io::Error::last_os_error();
+            match error.raw_os_error() {
+                // 内核不支持或被 seccomp 禁止时读取 /proc/<pid>/mem
+                Some(libc::ENOSYS) | Some(libc::EPERM) => {
+                    self.read_proc_mem(address, &mut buffer)?
+                }
+                Some(libc::ESRCH) => return Err(MemoryError::ProcessNotFound),
+                _ => {
+                    return Err(MemoryError::ReadMemoryFailed(format!(
+                        "{:#X}: {}",
+                        address, error
+                    )))
+                }
+            }
+        };
+        if read != size {
+            return Err(MemoryError::ShortRead {
+                address,
+                expected: size,
+                read,
+            });
+        }
+        Ok(buffer)
+    }
+
+    fn module_base(&self, module: Option<&str>) -> Result<usize, MemoryError> {
+        match module {
+            None => Ok(self.base_address),
+            Some(name) => std::fs::read_to_string(format!("/proc/{}/maps", self.pid))
+                .ok()
+                .and_then(|maps| module_base_in_maps(&maps, name))
+                .ok_or_else(|| MemoryError::Pointer(format!("找不到模块: {}", name))),
+        }
+    }
+
+    fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    fn is_alive(&self) -> bool {
+        match start_time(self.pid) {
+            Some(start) => self.start_time.unwrap_or(start) == start,
+            None => false,
+        }
+    }
+}
+
+// /proc/<pid>/stat 中的启动时间 (第 22 项), 进程不存在时返回 None
+fn start_time(pid: u32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // 进程名在括号中, 可能包含空格
+    let (_, rest) = stat.rsplit_once(')')?;
+    rest.split_whitespace().nth(19)?.parse().ok()
+}
+
+// 路径的文件名, wine 中的路径可能使用反斜杠
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+/**
+ * 在 /proc/<pid>/maps 的内容中查找模块的基址 (该文件最低的映射地址)
+ * 模块名不区分大小写
+ */
+pub fn module_base_in_maps(maps: &str, name: &str) -> Option<usize> {
+    maps.lines()
+        .filter_map(|line| {
+            // 地址范围 权限 偏移 设备 inode 路径, 路径中可能有空格
+            let (range, _) = line.split_once('-')?;
+            let mut path = line;
+            for _ in 0..5 {
+                path = path.trim_start().split_once(char::is_whitespace)?.1;
+            }
+            let path = path.trim();
+            if path.is_empty() || !file_name(path).eq_ignore_ascii_case(name) {
+                return None;
+            }
+            usize::from_str_radix(range, 16).ok()
+        })
+        .min()
+}
+
+/**
+ * 进程名: 第一个启动参数以 .exe 结尾时 (wine 中运行的程序) 使用该 exe 的文件名
+ * 否则使用可执行文件名
+ */
+pub fn process_name(exe: Option<&str>, cmdline: &[u8]) -> Option<String> {
+    let arg0 = cmdline.split(|b| *b == 0).next().unwrap_or_default();
+    let arg0 = String::from_utf8_lossy(arg0);
+    let arg0 = file_name(&arg0);
+    if arg0.to_ascii_lowercase().ends_with(".exe") {
+        return Some(arg0.to_string());
+    }
+    exe.map(|exe| file_name(exe).to_string())
+}
+
+/// 正在运行的所有进程, 读取不到可执行文件的进程 (内核线程或其他用户的进程) 不列出
+pub fn list_processes() -> Vec<ProcessInfo> {
+    let Ok(dir) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    dir.filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<u32>().ok())
+        .filter_map(|pid| {
+            let exe = std::fs::read_link(format!("/proc/{}/exe", pid)).ok();
+            let cmdline = std::fs::read(format!("/proc/{}/cmdline", pid)).unwrap_or_default();
+            let name = process_name(exe.as_ref().and_then(|exe| exe.to_str()), &cmdline)?;
+            Some(ProcessInfo { pid, name })
+        })
+        .collect()
+}
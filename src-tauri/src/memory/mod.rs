@@ -1 +1,8 @@
+pub mod attach;
+#[cfg(target_os = "linux")]
+pub mod linux;
+pub mod pointer;
+pub mod process;
+#[cfg(windows)]
 pub mod read;
+pub mod value;
@@ -0,0 +1,242 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Deserializer};
+
+use crate::{
+    memory::value::{PointerWidth, ValueType},
+    user::user::get_pointers_path,
+};
+
+/// 内置的指针链, 配置目录没有 pointers.json 时使用并写入该文件
+pub const DEFAULT_POINTERS: &str = r#"{
+  "pointer_size": 4,
+  "values": {
+    "mana": { "offsets": ["0xC910E4", "0x2C", "0xC", "0xA0"], "type": "u32" },
+    "skill": { "offsets": ["0xC912F0", "0x60"], "type": "u32" },
+    "lrf": { "offsets": ["0xC912F0", "0x25C", "slot"], "type": "u32", "params": ["slot"] },
+    "skill_plan": {
+      "offsets": ["0xC910E4", "0x2C", "0xC", "0x58B48", "0x17A80", "0x28", "slot", "0x10"],
+      "type": "u32",
+      "params": ["slot"]
+    }
+  }
+}"#;
+
+lazy_static! {
+    static ref POINTERS: RwLock<Option<Arc<PointerTable>>> = RwLock::new(None); // 第一次使用时读取
+}
+
+/// 指针链中的一个偏移, 可以是数字 (字符串支持 0x 开头的十六进制) 或参数名
+#[derive(Debug, Clone, PartialEq)]
+pub enum Offset {
+    Value(usize),
+    Param(String),
+}
+
+/// 解析数字, 支持 0x 开头的十六进制
+pub fn parse_number(s: &str) -> Option<usize> {
+    let s = s.trim();
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+impl Offset {
+    pub fn parse(s: &str) -> Offset {
+        match parse_number(s) {
+            Some(v) => Offset::Value(v),
+            None => Offset::Param(s.trim().to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Offset {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(usize),
+            Text(String),
+        }
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Number(v) => Offset::Value(v),
+            Raw::Text(s) => Offset::parse(&s),
+        })
+    }
+}
+
+/**
+ * 一个命名值的定义: 基址模块 + 偏移链 + 最终类型
+ * 除最后一个偏移外, 每一步读取 (当前地址 + 偏移) 处的指针, 最后读取 (当前地址 + 最后偏移) 处的值
+ */
+#[derive(Debug, Deserialize, Clone)]
+pub struct PointerDef {
+    pub module: Option<String>, // 基址模块, 为空时使用主程序
+    pub offsets: Vec<Offset>,
+    #[serde(default, rename = "type")]
+    pub value_type: ValueType,
+    pub length: Option<usize>, // bytes 和字符串的长度
+    #[serde(default)]
+    pub params: Vec<String>, // 参数名, 例如 skill_plan[3] 中的 3 替换偏移里的 slot
+}
+
+impl PointerDef {
+    /// 最终读取的字节数
+    pub fn size(&self) -> usize {
+        self.value_type.size(self.length.unwrap_or(0))
+    }
+
+    /// 用参数替换偏移中的参数名
+    pub fn offsets(&self, args: &[usize]) -> Result<Vec<usize>, String> {
+        if args.len() != self.params.len() {
+            return Err(format!(
+                "需要 {} 个参数, 传入了 {} 个",
+                self.params.len(),
+                args.len()
+            ));
+        }
+        self.offsets
+            .iter()
+            .map(|offset| match offset {
+                Offset::Value(v) => Ok(*v),
+                Offset::Param(name) => self
+                    .params
+                    .iter()
+                    .position(|p| p == name)
+                    .map(|i| args[i])
+                    .ok_or_else(|| format!("未定义的参数: {}", name)),
+            })
+            .collect()
+    }
+}
+
+/// 所有命名值的定义, 保存在配置目录的 pointers.json
+#[derive(Debug, Deserialize, Clone)]
+pub struct PointerTable {
+    #[serde(default)]
+    pub pointer_size: PointerWidth, // 指针字节数 4 或 8, 32 位游戏为 4
+    pub values: HashMap<String, PointerDef>,
+}
+
+impl Default for PointerTable {
+    fn default() -> Self {
+        // 内置的定义一定能解析
+        PointerTable::parse(DEFAULT_POINTERS).unwrap()
+    }
+}
+
+/// 解析 "skill_plan[3]" 或 "mana", 返回名称和参数
+pub fn parse_name(name: &str) -> Result<(&str, Vec<usize>), String> {
+    let name = name.trim();
+    let Some((base, rest)) = name.split_once('[') else {
+        return Ok((name, Vec::new()));
+    };
+    let args = rest
+        .strip_suffix(']')
+        .ok_or_else(|| format!("名称有误: {}", name))?;
+    let args = args
+        .split(',')
+        .map(|a| parse_number(a).ok_or_else(|| format!("参数有误: {}", name)))
+        .collect::<Result<_, _>>()?;
+    Ok((base.trim(), args))
+}
+
+impl PointerTable {
+    pub fn parse(json: &str) -> Result<PointerTable, String> {
+        let table: PointerTable =
+            serde_json::from_str(json).map_err(|e| format!("指针定义有误: {}", e))?;
+        for (name, def) in &table.values {
+            if def.offsets.is_empty() {
+                return Err(format!("{} 没有偏移", name));
+            }
+            if def.value_type.has_length() && def.length.unwrap_or(0) == 0 {
+                return Err(format!("{} 需要设置 length", name));
+            }
+            // 每个参数名都需要声明
+            def.offsets(&vec![0; def.params.len()])
+                .map_err(|e| format!("{}: {}", name, e))?;
+        }
+        Ok(table)
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> Result<PointerTable, String> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("读取指针定义失败 {}: {}", path.display(), e))?;
+        PointerTable::parse(&json)
+    }
+
+    /// 读取配置目录中的定义, 文件不存在时写入内置定义
+    pub fn load() -> Result<PointerTable, String> {
+        let path: PathBuf = get_pointers_path().ok_or_else(|| "找不到配置目录".to_string())?;
+        if !path.exists() {
+            if let Some(dir) = path.parent() {
+                let _ = std::fs::create_dir_all(dir);
+            }
+            std::fs::write(&path, DEFAULT_POINTERS)
+                .map_err(|e| format!("写入指针定义失败: {}", e))?;
+        }
+        PointerTable::open(path)
+    }
+
+    /// 查找名称对应的定义和替换参数后的偏移
+    pub fn lookup(&self, name: &str) -> Result<(&PointerDef, Vec<usize>), String> {
+        let (base, args) = parse_name(name)?;
+        let def = self
+            .values
+            .get(base)
+            .ok_or_else(|| format!("未定义的值: {}", base))?;
+        let offsets = def.offsets(&args).map_err(|e| format!("{}: {}", name, e))?;
+        Ok((def, offsets))
+    }
+}
+
+/**
+ * 沿偏移链查找最终地址, read_pointer 读取某个地址处的指针
+ * 中间读到空指针时返回错误
+ */
+pub fn walk<E: From<String>>(
+    base: usize,
+    offsets: &[usize],
+    mut read_pointer: impl FnMut(usize) -> Result<usize, E>,
+) -> Result<usize, E> {
+    let Some((last, chain)) = offsets.split_last() else {
+        return Ok(base);
+    };
+    let mut address = base;
+    for offset in chain {
+        let target = address.wrapping_add(*offset);
+        address = read_pointer(target)?;
+        if address == 0 {
+            return Err(E::from(format!("空指针: {:#X}", target)));
+        }
+    }
+    Ok(address.wrapping_add(*last))
+}
+
+/// 当前使用的指针定义, 第一次使用时从配置目录读取, 读取失败时使用内置定义
+pub fn pointers() -> Arc<PointerTable> {
+    if let Some(table) = POINTERS.read().unwrap().as_ref() {
+        return table.clone();
+    }
+    let table = Arc::new(PointerTable::load().unwrap_or_else(|e| {
+        eprintln!("{}, 使用内置指针定义", e);
+        PointerTable::default()
+    }));
+    *POINTERS.write().unwrap() = Some(table.clone());
+    table
+}
+
+/// 重新读取配置目录中的指针定义, 修改偏移后不需要重新编译, 返回定义的数量
+pub fn reload_pointers() -> Result<usize, String> {
+    let table = PointerTable::load()?;
+    let count = table.values.len();
+    *POINTERS.write().unwrap() = Some(Arc::new(table));
+    Ok(count)
+}
@@ -0,0 +1,207 @@
+use serde::Serialize;
+
+use crate::memory::{
+    pointer::{pointers, walk, PointerTable},
+    value::{from_bytes, FromBytes, MemoryValue, PointerWidth},
+};
+
+// 定义错误类型
+#[derive(Debug)]
+#[allow(dead_code)]
+pub enum MemoryError {
+    ProcessNotFound,
+    OpenProcessFailed(String),
+    ReadMemoryFailed(String),
+    PrivilegeAdjustFailed(String),
+    Pointer(String), // 指针定义有误、找不到模块或读到空指针
+    ShortRead {
+        address: usize,
+        expected: usize, // 需要读取的字节数
+        read: usize,     // 实际读取的字节数
+    },
+    Unsupported, // 当前系统不支持读取进程内存
+}
+
+impl From<String> for MemoryError {
+    fn from(e: String) -> Self {
+        MemoryError::Pointer(e)
+    }
+}
+
+/**
+ * 读取另一个进程的内存
+ * Windows 使用 ReadProcessMemory, Linux 使用 process_vm_readv
+ * 只需要实现 read_bytes 和 module_base, 指针链和命名值的读取由默认方法完成
+ */
+pub trait ProcessMemory: Send + Sync {
+    /// 读取 size 字节, 没有完整读取时返回 ShortRead 错误, 不返回部分数据
+    fn read_bytes(&self, address: usize, size: usize) -> Result<Vec<u8>, MemoryError>;
+
+    /// 模块的基址, 为空时使用主程序基址
+    fn module_base(&self, module: Option<&str>) -> Result<usize, MemoryError>;
+
+    /// 进程ID
+    fn pid(&self) -> u32;
+
+    /// 进程是否仍在运行, 同一个进程ID被重启后的进程使用时返回 false
+    fn is_alive(&self) -> bool;
+
+    /// 按类型读取, 例如 read::<i32>、read::<f32>、read::<bool>
+    #[allow(dead_code)]
+    fn read<T: FromBytes>(&self, address: usize) -> Result<T, MemoryError>
+    where
+        Self: Sized,
+    {
+        Ok(from_bytes(&self.read_bytes(address, T::SIZE)?)?)
+    }
+
+    /// 按目标进程的指针宽度读取指针
+    fn read_pointer(&self, address: usize, width: PointerWidth) -> Result<usize, MemoryError> {
+        Ok(width.decode(&self.read_bytes(address, width.size())?)?)
+    }
+
+    /// 按 table 中的定义读取命名值, 例如 "mana"、"skill_plan[3]"
+    fn read_value_in(&self, table: &PointerTable, name: &str) -> Result<MemoryValue, MemoryError> {
+        let (def, offsets) = table.lookup(name)?;
+        let base = self.module_base(def.module.as_deref())?;
+        let address = walk(base, &offsets, |address| {
+            self.read_pointer(address, table.pointer_size)
+        })?;
+        let bytes = self.read_bytes(address, def.size())?;
+        Ok(def.value_type.decode(&bytes)?)
+    }
+
+    /// 按 pointers.json 中的定义读取命名值
+    fn read_value(&self, name: &str) -> Result<MemoryValue, MemoryError> {
+        self.read_value_in(&pointers(), name)
+    }
+
+    /// 读取数值类型的命名值
+    fn read_number(&self, name: &str) -> Result<i64, MemoryError> {
+        self.read_value(name)?
+            .as_i64()
+            .ok_or_else(|| MemoryError::Pointer(format!("{} 不是数值", name)))
+    }
+
+    /// 读取技能名称
+    #[allow(dead_code)]
+    fn read_skill(&self) -> Result<u32, MemoryError> {
+        Ok(self.read_number("skill")? as u32)
+    }
+
+    /// 读取法力值
+    fn read_mana(&self) -> Result<usize, MemoryError> {
+        Ok(self.read_number("mana")? as usize)
+    }
+
+    // 左右键和F
+    fn read_lrf(&self, offset: usize) -> Result<usize, MemoryError> {
+        Ok(self.read_number(&format!("lrf[{}]", offset))? as usize)
+    }
+
+    fn read_skill_plan(&self, offset: usize) -> Result<usize, MemoryError> {
+        Ok(self.read_number(&format!("skill_plan[{}]", offset))? as usize)
+    }
+}
+
+/// 目标进程: 进程名和同时运行多个客户端时选择的进程ID
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TargetProcess {
+    pub name: String,     // 进程名, 不区分大小写
+    pub pid: Option<u32>, // 为空时使用第一个匹配的进程
+}
+
+impl TargetProcess {
+    pub fn new(name: &str) -> Self {
+        TargetProcess {
+            name: name.to_string(),
+            pid: None,
+        }
+    }
+}
+
+/// 正在运行的进程
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub name: String,
+}
+
+/// 正在运行的所有进程
+pub fn list_processes() -> Result<Vec<ProcessInfo>, MemoryError> {
+    #[cfg(windows)]
+    {
+        Ok(crate::memory::read::list_processes())
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Ok(crate::memory::linux::list_processes())
+    }
+    #[cfg(not(any(windows, target_os = "linux")))]
+    {
+        Err(MemoryError::Unsupported)
+    }
+}
+
+/// 进程名匹配的进程, 按进程ID排序, name 为空时返回所有进程
+pub fn find_processes(processes: Vec<ProcessInfo>, name: &str) -> Vec<ProcessInfo> {
+    let name = name.trim();
+    let mut found: Vec<ProcessInfo> = processes
+        .into_iter()
+        .filter(|p| name.is_empty() || p.name.eq_ignore_ascii_case(name))
+        .collect();
+    found.sort_by_key(|p| p.pid);
+    found
+}
+
+/**
+ * 选择目标进程的进程ID
+ * 指定了 pid 时该进程需要正在运行且进程名一致, 否则使用进程ID最小的匹配进程
+ */
+pub fn select_process(
+    processes: Vec<ProcessInfo>,
+    target: &TargetProcess,
+) -> Result<u32, MemoryError> {
+    let found = find_processes(processes, &target.name);
+    match target.pid {
+        Some(pid) => found
+            .iter()
+            .any(|p| p.pid == pid)
+            .then_some(pid)
+            .ok_or(MemoryError::ProcessNotFound),
+        None => found
+            .first()
+            .map(|p| p.pid)
+            .ok_or(MemoryError::ProcessNotFound),
+    }
+}
+
+/**
+ * 打开目标进程, base_address 为主程序基址
+ * Windows 使用 GameMemoryReader, Linux 使用 LinuxProcess
+ */
+pub fn open_process(
+    target: &TargetProcess,
+    base_address: usize,
+) -> Result<Box<dyn ProcessMemory>, MemoryError> {
+    let pid = select_process(list_processes()?, target)?;
+    #[cfg(windows)]
+    {
+        Ok(Box::new(crate::memory::read::GameMemoryReader::open(
+            pid,
+            base_address,
+        )?))
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Ok(Box::new(crate::memory::linux::LinuxProcess::attach(
+            pid,
+            base_address,
+        )))
+    }
+    #[cfg(not(any(windows, target_os = "linux")))]
+    {
+        let _ = (pid, base_address);
+        Err(MemoryError::Unsupported)
+    }
+}
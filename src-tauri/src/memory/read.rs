@@ -1,10 +1,11 @@
 use std::mem::{size_of, zeroed};
 use std::ptr::null_mut;
+use std::sync::Once;
 use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
 use windows::{
     core::Error,
     Win32::{
-        Foundation::{CloseHandle, HANDLE},
+        Foundation::{CloseHandle, HANDLE, STILL_ACTIVE},
         Security::{
             AdjustTokenPrivileges, LookupPrivilegeValueW, LUID_AND_ATTRIBUTES, SE_DEBUG_NAME,
             SE_PRIVILEGE_ENABLED, TOKEN_ADJUST_PRIVILEGES, TOKEN_PRIVILEGES, TOKEN_QUERY,
@@ -12,176 +13,103 @@ use windows::{
         System::{
             Diagnostics::Debug::ReadProcessMemory,
             Diagnostics::ToolHelp::{
-                CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
-                TH32CS_SNAPPROCESS,
+                CreateToolhelp32Snapshot, Module32FirstW, Module32NextW, Process32FirstW,
+                Process32NextW, MODULEENTRY32W, PROCESSENTRY32W, TH32CS_SNAPMODULE,
+                TH32CS_SNAPMODULE32, TH32CS_SNAPPROCESS,
             },
             Threading::{
-                OpenProcess, PROCESS_ALL_ACCESS, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ,
+                GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_VM_READ,
             },
         },
     },
 };
 
-// 定义错误类型
-#[derive(Debug)]
-#[allow(dead_code)]
-pub enum MemoryError {
-    ProcessNotFound,
-    OpenProcessFailed(Error),
-    ReadMemoryFailed(Error),
-    PrivilegeAdjustFailed(Error),
-    // 其他错误...
-}
+use crate::memory::process::{MemoryError, ProcessInfo, ProcessMemory};
 
 // 主结构体
 pub struct GameMemoryReader {
     process_handle: HANDLE,
     base_address: usize,
+    pid: u32,
 }
 
 impl GameMemoryReader {
-    /// 创建新的读取器并提权
-    pub fn new(process_name: &str, base_address: usize) -> Result<Self, MemoryError> {
-        // 提权操作
-        adjust_debug_privilege(true).expect("msg: 提权失败");
+    /// 打开进程ID为 pid 的进程, 第一次打开时提权
+    pub fn open(pid: u32, base_address: usize) -> Result<Self, MemoryError> {
+        // 提权操作只需要一次, 失败时仍可读取权限相同的进程
+        static PRIVILEGE: Once = Once::new();
+        PRIVILEGE.call_once(|| {
+            if let Err(e) = adjust_debug_privilege(true) {
+                eprintln!("提权失败: {:?}", e);
+            }
+        });
 
-        // 获取进程ID（此处简化为示例，实际需要枚举进程）
-        let pid = find_process_id(process_name).ok_or(MemoryError::ProcessNotFound)?;
-        // println!("进程ID: {}", pid);
-        // 打开进程句柄
+        // 只需要读取内存和查询进程是否退出
         let handle = unsafe {
             OpenProcess(
-                PROCESS_ALL_ACCESS | PROCESS_QUERY_INFORMATION | PROCESS_VM_READ,
+                PROCESS_VM_READ | PROCESS_QUERY_LIMITED_INFORMATION,
                 false,
                 pid,
             )
         }
-        .map_err(|e| MemoryError::OpenProcessFailed(e))?; // This ensures the variant is used
+        .map_err(|e| MemoryError::OpenProcessFailed(e.to_string()))?;
 
         Ok(Self {
             process_handle: handle,
             base_address,
+            pid,
         })
     }
+}
 
-    /// 读取链式地址
-    #[allow(dead_code)]
-    pub fn read_chain(&self, offsets: &[usize]) -> Result<usize, MemoryError> {
-        let mut current = self.base_address;
-        for &offset in offsets {
-            current = self.read_memory::<usize>(current + offset, None)?;
-        }
-        Ok(current)
-    }
-
-    /// 泛型内存读取方法
-    pub fn read_memory<T: Copy + Default>(
-        &self,
-        address: usize,
-        byte_size: Option<usize>,
-    ) -> Result<T, MemoryError> {
-        let mut buffer: T = Default::default();
-        let size = byte_size.unwrap_or(size_of::<T>());
-        // println!("读取地址: {:#X}, 大小: {}", address, size); // 添加调试日志
-
+/// Windows 使用 ReadProcessMemory 读取
+impl ProcessMemory for GameMemoryReader {
+    fn read_bytes(&self, address: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
+        let mut buffer = vec![0u8; size];
+        let mut read = 0usize;
         unsafe {
-            // 读取内存
-            if ReadProcessMemory(
+            ReadProcessMemory(
                 self.process_handle,
                 address as _,
-                &mut buffer as *mut T as _,
+                buffer.as_mut_ptr() as _,
                 size,
-                None,
+                Some(&mut read),
             )
-            .is_ok()
-            {
-                // 根据类型返回结果
-                Ok(buffer)
-            } else {
-                Err(MemoryError::ReadMemoryFailed(Error::from_win32()))
-            }
+            .map_err(|e| MemoryError::ReadMemoryFailed(e.to_string()))?;
         }
-    }
-
-    /// 读取技能名称
-    #[allow(dead_code)]
-    pub fn read_skill(&self) -> Result<u32, MemoryError> {
-        let skill_address = self.base_address + 0x00C912F0;
-        let offsets = 0x60;
-        let mut current_address = skill_address;
-        let read_memory_data = self.read_memory::<u32>(current_address, Some(4))?;
-        current_address = read_memory_data as usize + offsets;
-
-        // 最终读取技能 ID
-        let skill_id = self.read_memory::<u32>(current_address, Some(4))?;
-
-        Ok(skill_id)
-    }
-
-    /// 读取法力值
-    pub fn read_mana(&self) -> Result<usize, MemoryError> {
-        let offsets = [0x00C910E4, 0x2C, 0xC, 0xA0];
-        let mut current_address = self.base_address;
-
-        for &offset in &offsets {
-            // 检查当前地址是否有效
-            if current_address == 0 {
-                return Err(MemoryError::ReadMemoryFailed(Error::from_win32()));
-            }
-
-            // 读取当前地址的值
-            current_address = self.read_memory::<usize>(current_address + offset, Some(4))?;
+        if read != size {
+            return Err(MemoryError::ShortRead {
+                address,
+                expected: size,
+                read,
+            });
         }
-
-        // 最终读取法力值
-        Ok(current_address)
+        Ok(buffer)
     }
 
-    // 左右键和F
-    #[allow(dead_code)]
-    pub fn read_lrf(&self, offset: usize) -> Result<usize, MemoryError> {
-        // println!("读取技能偏移量: {}", offset);
-        // offset10进制转16进制
-        // println!("技能偏移量(16进制): {:#X}", offset);
-        // let offsets = [0x00C912F0, 0x248, 0x8, 0x4, 0x8, 0x0, 0x30, offset];
-        let offsets = [0x00C912F0, 0x25C, offset];
-        let mut current_address = self.base_address;
-
-        for &offset in &offsets {
-            // 检查当前地址是否有效
-            if current_address == 0 {
-                return Err(MemoryError::ReadMemoryFailed(Error::from_win32()));
-            }
-
-            // 读取当前地址的值
-            current_address = self.read_memory::<usize>(current_address + offset, Some(4))?;
+    fn module_base(&self, module: Option<&str>) -> Result<usize, MemoryError> {
+        match module {
+            None => Ok(self.base_address),
+            Some(name) => find_module_base(self.pid, name)
+                .ok_or_else(|| MemoryError::Pointer(format!("找不到模块: {}", name))),
         }
-
-        Ok(current_address)
     }
 
-    #[allow(dead_code)]
-    pub fn read_skill_plan(&self, offset: usize) -> Result<usize, MemoryError> {
-        let offsets = [
-            0x00C910E4, 0x2C, 0x0C, 0x00058B48, 0x00017A80, 0x28, offset, 0x10,
-        ];
-        let mut current_address = self.base_address;
-
-        for &offset in &offsets {
-            // 检查当前地址是否有效
-            if current_address == 0 {
-                return Err(MemoryError::ReadMemoryFailed(Error::from_win32()));
-            }
-
-            // 读取当前地址的值
-            current_address = self.read_memory::<usize>(current_address + offset, Some(4))?;
-        }
+    fn pid(&self) -> u32 {
+        self.pid
+    }
 
-        // 最终读取技能计划
-        Ok(current_address)
+    fn is_alive(&self) -> bool {
+        let mut code = 0u32;
+        unsafe { GetExitCodeProcess(self.process_handle, &mut code) }.is_ok()
+            && code == STILL_ACTIVE.0 as u32
     }
 }
 
+// 进程句柄可以在线程间共享, 读取不修改状态
+unsafe impl Send for GameMemoryReader {}
+unsafe impl Sync for GameMemoryReader {}
+
 impl Drop for GameMemoryReader {
     fn drop(&mut self) {
         unsafe { CloseHandle(self.process_handle).ok() };
@@ -199,12 +127,16 @@ fn adjust_debug_privilege(enable: bool) -> Result<(), MemoryError> {
         )
         .is_ok()
         {
-            return Err(MemoryError::PrivilegeAdjustFailed(Error::from_win32()));
+            return Err(MemoryError::PrivilegeAdjustFailed(
+                Error::from_win32().to_string(),
+            ));
         }
 
         let mut luid = zeroed();
         if !LookupPrivilegeValueW(None, SE_DEBUG_NAME, &mut luid).is_ok() {
-            return Err(MemoryError::PrivilegeAdjustFailed(Error::from_win32()));
+            return Err(MemoryError::PrivilegeAdjustFailed(
+                Error::from_win32().to_string(),
+            ));
         }
 
         let mut tp = TOKEN_PRIVILEGES {
@@ -229,22 +161,26 @@ fn adjust_debug_privilege(enable: bool) -> Result<(), MemoryError> {
         )
         .is_ok()
         {
-            Err(MemoryError::PrivilegeAdjustFailed(Error::from_win32()))
+            Err(MemoryError::PrivilegeAdjustFailed(
+                Error::from_win32().to_string(),
+            ))
         } else {
             Ok(())
         }
     }
 }
 
-fn find_process_id(name: &str) -> Option<u32> {
+/// 正在运行的所有进程
+pub fn list_processes() -> Vec<ProcessInfo> {
+    let mut processes = Vec::new();
     unsafe {
         // 创建进程快照
         let snapshot = match CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) {
             Ok(handle) => handle,
-            Err(_) => return None,
+            Err(_) => return processes,
         };
         if snapshot.is_invalid() {
-            return None;
+            return processes;
         }
 
         let mut entry = PROCESSENTRY32W {
@@ -255,20 +191,11 @@ fn find_process_id(name: &str) -> Option<u32> {
         // 遍历进程列表
         if Process32FirstW(snapshot, &mut entry).is_ok() {
             loop {
-                // 将进程名称转换为 Rust 字符串
-                let process_name = String::from_utf16_lossy(
-                    &entry
-                        .szExeFile
-                        .iter()
-                        .take_while(|c| **c != 0u16)
-                        .map(|&c| c)
-                        .collect::<Vec<u16>>(),
-                );
-
-                // 检查是否匹配目标进程名称
-                if process_name.eq_ignore_ascii_case(name) {
-                    return Some(entry.th32ProcessID);
-                }
+                let len = entry.szExeFile.iter().position(|c| *c == 0).unwrap_or(0);
+                processes.push(ProcessInfo {
+                    pid: entry.th32ProcessID,
+                    name: String::from_utf16_lossy(&entry.szExeFile[..len]),
+                });
 
                 // 获取下一个进程
                 if !Process32NextW(snapshot, &mut entry).is_ok() {
@@ -276,7 +203,38 @@ fn find_process_id(name: &str) -> Option<u32> {
                 }
             }
         }
+        let _ = CloseHandle(snapshot);
+    }
+    processes
+}
 
-        None
+// 进程中模块的基址, 模块名不区分大小写
+fn find_module_base(pid: u32, name: &str) -> Option<usize> {
+    unsafe {
+        let snapshot =
+            CreateToolhelp32Snapshot(TH32CS_SNAPMODULE | TH32CS_SNAPMODULE32, pid).ok()?;
+        if snapshot.is_invalid() {
+            return None;
+        }
+        let mut entry = MODULEENTRY32W {
+            dwSize: size_of::<MODULEENTRY32W>() as u32,
+            ..Default::default()
+        };
+        let mut base = None;
+        if Module32FirstW(snapshot, &mut entry).is_ok() {
+            loop {
+                let len = entry.szModule.iter().position(|c| *c == 0).unwrap_or(0);
+                let module_name = String::from_utf16_lossy(&entry.szModule[..len]);
+                if module_name.eq_ignore_ascii_case(name) {
+                    base = Some(entry.modBaseAddr as usize);
+                    break;
+                }
+                if Module32NextW(snapshot, &mut entry).is_err() {
+                    break;
+                }
+            }
+        }
+        let _ = CloseHandle(snapshot);
+        base
     }
 }
@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+
+/// 目标进程的指针宽度, 32 位游戏为 4 字节
+#[derive(Debug, Deserialize, Clone, Copy, Serialize, PartialEq, Default)]
+#[serde(try_from = "usize", into = "usize")]
+pub enum PointerWidth {
+    #[default]
+    Bits32,
+    Bits64,
+}
+
+impl TryFrom<usize> for PointerWidth {
+    type Error = String;
+
+    fn try_from(size: usize) -> Result<Self, Self::Error> {
+        match size {
+            4 => Ok(PointerWidth::Bits32),
+            8 => Ok(PointerWidth::Bits64),
+            _ => Err(format!("指针字节数只支持 4 或 8: {}", size)),
+        }
+    }
+}
+
+impl From<PointerWidth> for usize {
+    fn from(width: PointerWidth) -> usize {
+        width.size()
+    }
+}
+
+impl PointerWidth {
+    pub fn size(self) -> usize {
+        match self {
+            PointerWidth::Bits32 => 4,
+            PointerWidth::Bits64 => 8,
+        }
+    }
+
+    /// 按小端解析指针, 字节数需要和宽度一致
+    pub fn decode(self, bytes: &[u8]) -> Result<usize, String> {
+        let address = match self {
+            PointerWidth::Bits32 => from_bytes::<u32>(bytes)? as u64,
+            PointerWidth::Bits64 => from_bytes::<u64>(bytes)?,
+        };
+        usize::try_from(address).map_err(|_| format!("地址超出范围: {:#X}", address))
+    }
+}
+
+/**
+ * 可以从内存字节按小端解析的类型
+ */
+pub trait FromBytes: Sized {
+    const SIZE: usize;
+    fn from_le(bytes: &[u8]) -> Self; // bytes 的长度等于 SIZE
+}
+
+macro_rules! impl_from_bytes {
+    ($($t:ty),*) => {
+        $(
+            impl FromBytes for $t {
+                const SIZE: usize = std::mem::size_of::<$t>();
+                fn from_le(bytes: &[u8]) -> Self {
+                    let mut buf = [0u8; std::mem::size_of::<$t>()];
+                    buf.copy_from_slice(bytes);
+                    <$t>::from_le_bytes(buf)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_bytes!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
+impl FromBytes for bool {
+    const SIZE: usize = 1;
+    fn from_le(bytes: &[u8]) -> Self {
+        bytes[0] != 0
+    }
+}
+
+/// 解析固定大小的类型, 字节数不一致时返回错误而不是部分数据
+pub fn from_bytes<T: FromBytes>(bytes: &[u8]) -> Result<T, String> {
+    if bytes.len() != T::SIZE {
+        return Err(format!("需要 {} 字节, 实际 {} 字节", T::SIZE, bytes.len()));
+    }
+    Ok(T::from_le(bytes))
+}
+
+/// 命名值最终读取的类型
+#[derive(Debug, Deserialize, Clone, Copy, Serialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueType {
+    U8,
+    I8,
+    U16,
+    I16,
+    #[default]
+    U32,
+    I32,
+    U64,
+    I64,
+    F32,
+    F64,
+    Bool,
+    Bytes, // 固定长度的字节, 长度由 length 指定
+    Utf8,  // 以下字符串遇到 0 结束, length 为最大字节数
+    Utf16, // length 为最大字符数, 每个字符 2 字节
+    Gbk,
+}
+
+/// 读取到的值
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum MemoryValue {
+    Int(i64),
+    UInt(u64), // u64 超出 i64 范围时不丢失
+    Float(f64),
+    Bool(bool),
+    Bytes(Vec<u8>),
+    Text(String),
+}
+
+impl ValueType {
+    /// 是否需要 length
+    pub fn has_length(self) -> bool {
+        matches!(
+            self,
+            ValueType::Bytes | ValueType::Utf8 | ValueType::Utf16 | ValueType::Gbk
+        )
+    }
+
+    /// 读取的字节数, 固定大小的类型忽略 length
+    pub fn size(self, length: usize) -> usize {
+        match self {
+            ValueType::U8 | ValueType::I8 | ValueType::Bool => 1,
+            ValueType::U16 | ValueType::I16 => 2,
+            ValueType::U32 | ValueType::I32 | ValueType::F32 => 4,
+            ValueType::U64 | ValueType::I64 | ValueType::F64 => 8,
+            ValueType::Bytes | ValueType::Utf8 | ValueType::Gbk => length,
+            ValueType::Utf16 => length * 2,
+        }
+    }
+
+    /**
+     * 按小端解析读取到的字节
+     * 固定大小的类型字节数不一致时返回错误, 字符串在第一个 0 处结束
+     */
+    pub fn decode(self, bytes: &[u8]) -> Result<MemoryValue, String> {
+        let value = match self {
+            ValueType::U8 => MemoryValue::Int(from_bytes::<u8>(bytes)? as i64),
+            ValueType::I8 => MemoryValue::Int(from_bytes::<i8>(bytes)? as i64),
+            ValueType::U16 => MemoryValue::Int(from_bytes::<u16>(bytes)? as i64),
+            ValueType::I16 => MemoryValue::Int(from_bytes::<i16>(bytes)? as i64),
+            ValueType::U32 => MemoryValue::Int(from_bytes::<u32>(bytes)? as i64),
+            ValueType::I32 => MemoryValue::Int(from_bytes::<i32>(bytes)? as i64),
+            ValueType::U64 => MemoryValue::UInt(from_bytes::<u64>(bytes)?),
+            ValueType::I64 => MemoryValue::Int(from_bytes::<i64>(bytes)?),
+            ValueType::F32 => MemoryValue::Float(from_bytes::<f32>(bytes)? as f64),
+            ValueType::F64 => MemoryValue::Float(from_bytes::<f64>(bytes)?),
+            ValueType::Bool => MemoryValue::Bool(from_bytes::<bool>(bytes)?),
+            ValueType::Bytes => MemoryValue::Bytes(bytes.to_vec()),
+            ValueType::Utf8 => {
+                MemoryValue::Text(String::from_utf8_lossy(until_nul(bytes)).into_owned())
+            }
+            ValueType::Gbk => {
+                let (text, _) = encoding_rs::GBK.decode_without_bom_handling(until_nul(bytes));
+                MemoryValue::Text(text.into_owned())
+            }
+            ValueType::Utf16 => {
+                if bytes.len() % 2 == 1 {
+                    return Err(format!("utf16 字节数需要是偶数: {}", bytes.len()));
+                }
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .take_while(|c| *c != 0)
+                    .collect();
+                MemoryValue::Text(String::from_utf16_lossy(&units))
+            }
+        };
+        Ok(value)
+    }
+}
+
+// 第一个 0 之前的字节
+fn until_nul(bytes: &[u8]) -> &[u8] {
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    &bytes[..end]
+}
+
+impl MemoryValue {
+    /// 数值, 字节和字符串返回 None
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            MemoryValue::Int(v) => Some(*v),
+            MemoryValue::UInt(v) => i64::try_from(*v).ok(),
+            MemoryValue::Float(v) => Some(*v as i64),
+            MemoryValue::Bool(v) => Some(*v as i64),
+            MemoryValue::Bytes(_) | MemoryValue::Text(_) => None,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            MemoryValue::Int(v) => Some(*v as f64),
+            MemoryValue::UInt(v) => Some(*v as f64),
+            MemoryValue::Float(v) => Some(*v),
+            MemoryValue::Bool(v) => Some(*v as i64 as f64),
+            MemoryValue::Bytes(_) | MemoryValue::Text(_) => None,
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{
+    global::{
+        common::RGB,
+        model::{Bar, BarDirection, ColorMetric},
+    },
+    rgb::{check::parse_rgb, metric::color_matches, pattern::parse_region},
+};
+
+/**
+ * 血条/蓝条/体力条, 按填充颜色计算填充百分比
+ * 逐列判断是否已填充 (一半以上的像素匹配), 从空的一端往回找到第一列已填充的列作为边界
+ * 血条中间的数字或图标遮住部分像素时不影响结果
+ */
+#[derive(Debug, Clone)]
+pub struct BarGauge {
+    pub region: (i32, i32, u32, u32), // x, y, 宽, 高
+    pub rgb: RGB,
+    pub tolerance: f64,
+    pub metric: ColorMetric,
+    pub saturation: Option<f64>,
+    pub direction: BarDirection,
+    pub below: Option<f64>,
+    pub above: Option<f64>,
+}
+
+impl BarGauge {
+    pub fn parse(bar: &Bar) -> Result<BarGauge, String> {
+        let region = parse_region(&bar.bar).ok_or_else(|| format!("区域有误: {}", bar.bar))?;
+        let rgb = parse_rgb(&bar.rgb).ok_or_else(|| format!("颜色有误: {}", bar.rgb))?;
+        if bar.below.is_none() && bar.above.is_none() {
+            return Err("血条需要设置 below 或 above".to_string());
+        }
+        Ok(BarGauge {
+            region,
+            rgb,
+            tolerance: bar.tolerance,
+            metric: bar.metric,
+            saturation: bar.saturation,
+            direction: bar.direction,
+            below: bar.below,
+            above: bar.above,
+        })
+    }
+
+    /// 区域左上角和右下角, 用于计算截图范围
+    pub fn corners(&self) -> [(i32, i32); 2] {
+        let (x, y, width, height) = self.region;
+        [(x, y), (x + width as i32 - 1, y + height as i32 - 1)]
+    }
+
+    /// 第 column 列是否已填充, pixel 读取不到的像素算作不匹配
+    pub fn column_filled(&self, column: u32, pixel: impl Fn(i32, i32) -> Option<RGB>) -> bool {
+        let (x, y, _, height) = self.region;
+        let matched = (0..height as i32)
+            .filter_map(|dy| pixel(x + column as i32, y + dy))
+            .filter(|c| color_matches(self.metric, *c, self.rgb, self.tolerance, self.saturation))
+            .count();
+        matched * 2 > height as usize
+    }
+
+    /// 填充百分比 0-100
+    pub fn fill(&self, pixel: impl Fn(i32, i32) -> Option<RGB>) -> f64 {
+        let width = self.region.2;
+        let filled = match self.direction {
+            // 从右往左找到的第一列已填充的列就是边界
+            BarDirection::LeftToRight => (0..width)
+                .rev()
+                .find(|c| self.column_filled(*c, &pixel))
+                .map_or(0, |c| c + 1),
+            BarDirection::RightToLeft => (0..width)
+                .find(|c| self.column_filled(*c, &pixel))
+                .map_or(0, |c| width - c),
+        };
+        filled as f64 * 100.0 / width as f64
+    }
+
+    /// 填充百分比是否满足 below/above, 两个都设置时需要同时满足
+    pub fn holds(&self, fill: f64) -> bool {
+        self.below.is_none_or(|below| fill < below) && self.above.is_none_or(|above| fill > above)
+    }
+
+    pub fn check(&self, pixel: impl Fn(i32, i32) -> Option<RGB>) -> bool {
+        self.holds(self.fill(pixel))
+    }
+}
@@ -0,0 +1,141 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    global::common::RGB,
+    rgb::{
+        monitor::virtual_bounds,
+        source::{Frame, ScreenSource},
+    },
+};
+
+/// 包含所有坐标的最小区域 (x, y, 宽, 高)
+pub fn bounding_region(points: &[(i32, i32)]) -> Option<(i32, i32, u32, u32)> {
+    let min_x = points.iter().map(|p| p.0).min()?;
+    let min_y = points.iter().map(|p| p.1).min()?;
+    let max_x = points.iter().map(|p| p.0).max()?;
+    let max_y = points.iter().map(|p| p.1).max()?;
+    Some((
+        min_x,
+        min_y,
+        (max_x - min_x) as u32 + 1,
+        (max_y - min_y) as u32 + 1,
+    ))
+}
+
+/// 同时包含两个区域的最小区域
+fn union(a: (i32, i32, u32, u32), b: (i32, i32, u32, u32)) -> (i32, i32, u32, u32) {
+    let left = a.0.min(b.0);
+    let top = a.1.min(b.1);
+    let right = (a.0 + a.2 as i32).max(b.0 + b.2 as i32);
+    let bottom = (a.1 + a.3 as i32).max(b.1 + b.3 as i32);
+    (left, top, (right - left) as u32, (bottom - top) as u32)
+}
+
+/// 两个区域重叠的部分, 不重叠时返回 None
+fn intersect(a: (i32, i32, u32, u32), b: (i32, i32, u32, u32)) -> Option<(i32, i32, u32, u32)> {
+    let left = a.0.max(b.0);
+    let top = a.1.max(b.1);
+    let right = (a.0 + a.2 as i32).min(b.0 + b.2 as i32);
+    let bottom = (a.1 + a.3 as i32).min(b.1 + b.3 as i32);
+    if right <= left || bottom <= top {
+        return None;
+    }
+    Some((left, top, (right - left) as u32, (bottom - top) as u32))
+}
+
+/**
+ * 一轮执行内共用的截图
+ * 第一次取色时截取规则用到的所有坐标所在的区域, 同一轮的取色都从这张截图读取
+ * 设置了 max_age 时截图可以跨轮复用, 超过 max_age 后重新截取
+ */
+pub struct FrameCache {
+    region: Option<(i32, i32, u32, u32)>,
+    max_age: Option<Duration>,
+    frame: Option<(Frame, Instant)>,
+}
+
+impl FrameCache {
+    pub fn new(points: &[(i32, i32)], max_age: Option<Duration>) -> Self {
+        FrameCache {
+            region: bounding_region(points),
+            max_age,
+            frame: None,
+        }
+    }
+
+    /**
+     * 扩大截图区域, 使其包含 region 在 bounds 内的部分
+     * 开启调试截图时用来截取取色坐标周围的画面, bounds 为所有显示器组成的区域
+     */
+    pub fn widen(&mut self, region: (i32, i32, u32, u32), bounds: (i32, i32, u32, u32)) {
+        if let Some(extra) = intersect(region, bounds) {
+            self.region = Some(match self.region {
+                Some(current) => union(current, extra),
+                None => extra,
+            });
+            self.frame = None;
+        }
+    }
+
+    /// 新一轮执行开始, 没有设置 max_age 时丢弃上一轮的截图
+    pub fn begin_cycle(&mut self) {
+        if self.max_age.is_none() {
+            self.frame = None;
+        }
+    }
+
+    /// 当前截图, 不存在或过期时重新截取, 没有取色坐标时返回 None
+    pub fn frame(
+        &mut self,
+        source: &dyn ScreenSource,
+        now: Instant,
+    ) -> Result<Option<&Frame>, String> {
+        let (x, y, width, height) = match self.region {
+            Some(region) => region,
+            None => return Ok(None),
+        };
+        let expired = match (&self.frame, self.max_age) {
+            (None, _) => true,
+            (Some((_, captured_at)), Some(max_age)) => {
+                now.saturating_duration_since(*captured_at) > max_age
+            }
+            (Some(_), None) => false,
+        };
+        if expired {
+            let frame = match source.region(x, y, width, height) {
+                Ok(frame) => frame,
+                Err(e) => {
+                    // 有坐标超出屏幕时只截取在显示器内的部分, 之后都截取这部分
+                    let visible = source
+                        .monitors()
+                        .ok()
+                        .and_then(|monitors| virtual_bounds(&monitors))
+                        .and_then(|bounds| intersect((x, y, width, height), bounds))
+                        .filter(|visible| *visible != (x, y, width, height))
+                        .ok_or(e)?;
+                    let frame = source.region(visible.0, visible.1, visible.2, visible.3)?;
+                    self.region = Some(visible);
+                    frame
+                }
+            };
+            self.frame = Some((frame, now));
+        }
+        Ok(self.frame.as_ref().map(|(frame, _)| frame))
+    }
+
+    /// 读取像素, 截图不存在或过期时重新截取
+    pub fn pixel(
+        &mut self,
+        source: &dyn ScreenSource,
+        x: i32,
+        y: i32,
+        now: Instant,
+    ) -> Result<RGB, String> {
+        // 截图失败时不影响其他坐标, 和不在截图区域内的坐标一样单独读取
+        let frame = self.frame(source, now).ok().flatten();
+        match frame.and_then(|frame| frame.pixel(x, y)) {
+            Some(rgb) => Ok(rgb),
+            None => source.pixel(x, y),
+        }
+    }
+}
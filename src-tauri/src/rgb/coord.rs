@@ -0,0 +1,262 @@
+use crate::{
+    global::model::{Children, CoordinateSpace, ElementEnum, Elements, WatchCondition},
+    rgb::{
+        check::parse_coordinate,
+        monitor::{find_monitor, Monitor},
+        pattern::parse_region,
+    },
+};
+
+/**
+ * 坐标换算, 记录的坐标先按比例缩放再加上原点
+ * 用于不同分辨率、缩放比例或窗口位置下运行同一份规则
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub origin_x: i32, // 原点, 窗口坐标时为客户区左上角
+    pub origin_y: i32, //
+    pub scale_x: f64,  // 横向缩放比例
+    pub scale_y: f64,  // 纵向缩放比例
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        origin_x: 0,
+        origin_y: 0,
+        scale_x: 1.0,
+        scale_y: 1.0,
+    };
+
+    /// 把 from 大小的区域映射到 to 区域 (x, y, 宽, 高)
+    pub fn scaled(from: (u32, u32), to: (i32, i32, u32, u32)) -> Transform {
+        let (x, y, width, height) = to;
+        Transform {
+            origin_x: x,
+            origin_y: y,
+            scale_x: width as f64 / from.0.max(1) as f64,
+            scale_y: height as f64 / from.1.max(1) as f64,
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Transform::IDENTITY
+    }
+
+    /// 换算坐标
+    pub fn point(&self, x: i32, y: i32) -> (i32, i32) {
+        let (dx, dy) = self.offset(x, y);
+        (self.origin_x + dx, self.origin_y + dy)
+    }
+
+    /// 换算相对偏移, 只缩放不加原点
+    pub fn offset(&self, dx: i32, dy: i32) -> (i32, i32) {
+        (
+            (dx as f64 * self.scale_x).round() as i32,
+            (dy as f64 * self.scale_y).round() as i32,
+        )
+    }
+
+    /// 换算区域, 两个角分别换算, 宽高至少为 1
+    pub fn region(&self, region: (i32, i32, u32, u32)) -> (i32, i32, u32, u32) {
+        let (x, y, width, height) = region;
+        let (x1, y1) = self.point(x, y);
+        let (x2, y2) = self.point(x + width as i32, y + height as i32);
+        (x1, y1, (x2 - x1).max(1) as u32, (y2 - y1).max(1) as u32)
+    }
+
+    /// 换算 "x,y" 格式的坐标, 格式有误时原样返回
+    pub fn coordinate(&self, coordinate: &str) -> String {
+        match parse_coordinate(coordinate) {
+            Some((x, y)) => {
+                let (x, y) = self.point(x, y);
+                format!("{},{}", x, y)
+            }
+            None => coordinate.to_string(),
+        }
+    }
+
+    /// 换算 "x,y,宽,高" 格式的区域, 格式有误时原样返回
+    pub fn region_str(&self, region: &str) -> String {
+        match parse_region(region) {
+            Some(r) => {
+                let (x, y, width, height) = self.region(r);
+                format!("{},{},{},{}", x, y, width, height)
+            }
+            None => region.to_string(),
+        }
+    }
+}
+
+/**
+ * 根据坐标的记录方式计算换算方式
+ * screen: 主显示器大小, monitors: 当前的显示器布局
+ * window: 按标题查找窗口客户区 (x, y, 宽, 高)
+ */
+pub fn transform_for(
+    space: &CoordinateSpace,
+    screen: (u32, u32),
+    monitors: &[Monitor],
+    window: impl Fn(&str) -> Result<(i32, i32, u32, u32), String>,
+) -> Result<Transform, String> {
+    match space {
+        CoordinateSpace::Screen => Ok(Transform::IDENTITY),
+        CoordinateSpace::Reference { width, height } => {
+            if screen.0 == 0 || screen.1 == 0 {
+                return Err("读取不到屏幕大小".to_string());
+            }
+            Ok(Transform::scaled(
+                (*width, *height),
+                (0, 0, screen.0, screen.1),
+            ))
+        }
+        CoordinateSpace::Monitor {
+            monitor,
+            width,
+            height,
+        } => {
+            let m = find_monitor(monitors, monitor)
+                .ok_or_else(|| format!("找不到显示器: {}", monitor))?;
+            let from = (width.unwrap_or(m.width), height.unwrap_or(m.height));
+            Ok(Transform::scaled(from, m.rect()))
+        }
+        CoordinateSpace::Window {
+            title,
+            width,
+            height,
+        } => {
+            let rect = window(title)?;
+            let (x, y, client_width, client_height) = rect;
+            // 没有记录客户区大小时只平移
+            let from = (
+                width.unwrap_or(client_width),
+                height.unwrap_or(client_height),
+            );
+            Ok(Transform::scaled(from, (x, y, client_width, client_height)))
+        }
+    }
+}
+
+/**
+ * 相对窗口记录的坐标在执行时换算
+ * 每次执行前读取窗口位置, 窗口移动或改变大小后重新换算
+ */
+pub struct WindowTracker {
+    space: CoordinateSpace,
+    rect: Option<(i32, i32, u32, u32)>, // 上次换算时的窗口客户区
+}
+
+impl WindowTracker {
+    /// 只有相对窗口的坐标需要在执行时换算, 其他方式在开启时已经换算
+    pub fn new(space: &CoordinateSpace) -> Option<WindowTracker> {
+        match space {
+            CoordinateSpace::Window { .. } => Some(WindowTracker {
+                space: space.clone(),
+                rect: None,
+            }),
+            _ => None,
+        }
+    }
+
+    /// 读取窗口当前的客户区, 和上次换算时不同时返回新的换算方式
+    pub fn update(
+        &mut self,
+        window: impl Fn(&str) -> Result<(i32, i32, u32, u32), String>,
+    ) -> Result<Option<Transform>, String> {
+        let rect = match &self.space {
+            CoordinateSpace::Window { title, .. } => window(title)?,
+            _ => return Ok(None),
+        };
+        if self.rect == Some(rect) {
+            return Ok(None);
+        }
+        self.rect = Some(rect);
+        transform_for(&self.space, (0, 0), &[], |_| Ok(rect)).map(Some)
+    }
+}
+
+/// 换算流程中所有节点的坐标
+pub fn transform_children(children: &mut [Children], t: &Transform) {
+    for c in children.iter_mut() {
+        match &mut c.element {
+            ElementEnum::Color(co) => co.coordinate = t.coordinate(&co.coordinate),
+            ElementEnum::ColorPattern(cp) => {
+                cp.anchor = cp.anchor.as_deref().map(|a| t.coordinate(a));
+                cp.search = cp.search.as_deref().map(|s| t.region_str(s));
+                for p in cp.points.iter_mut() {
+                    (p.dx, p.dy) = t.offset(p.dx, p.dy);
+                }
+            }
+            // 模板图片本身不缩放, 分辨率不同时需要重新截取模板
+            ElementEnum::Template(tp) => tp.region = t.region_str(&tp.region),
+            ElementEnum::Bar(b) => b.bar = t.region_str(&b.bar),
+            // 字形不缩放, 分辨率不同时需要重新保存字形
+            ElementEnum::Digits(d) => d.region = t.region_str(&d.region),
+            ElementEnum::Mouse(m) => m.position = m.position.as_deref().map(|p| t.coordinate(p)),
+            _ => {}
+        }
+        if let Some(children) = &mut c.children {
+            transform_children(children, t);
+        }
+    }
+}
+
+/**
+ * 换算规则中的坐标, 包括主流程、轻按流程和像素条件
+ */
+pub fn transform_elements(e: &mut Elements, t: &Transform) {
+    if t.is_identity() {
+        return;
+    }
+    if let Some(children) = &mut e.children {
+        transform_children(children, t);
+    }
+    if let Some(children) = &mut e.tap_children {
+        transform_children(children, t);
+    }
+    if let Some(w) = &mut e.watch {
+        transform_condition(&mut w.condition, t);
+    }
+}
+
+/// 换算检测条件中的坐标
+pub fn transform_condition(condition: &mut WatchCondition, t: &Transform) {
+    if let WatchCondition::Pixel { coordinate, .. } = condition {
+        *coordinate = t.coordinate(coordinate);
+    }
+}
+
+/**
+ * 窗口客户区在屏幕上的位置和大小 (x, y, 宽, 高)
+ */
+#[cfg(windows)]
+pub fn window_client_rect(title: &str) -> Result<(i32, i32, u32, u32), String> {
+    use windows::{
+        core::{HSTRING, PCWSTR},
+        Win32::{
+            Foundation::{POINT, RECT},
+            Graphics::Gdi::ClientToScreen,
+            UI::WindowsAndMessaging::{FindWindowW, GetClientRect},
+        },
+    };
+    unsafe {
+        let hwnd = FindWindowW(PCWSTR::null(), &HSTRING::from(title))
+            .map_err(|_| format!("找不到窗口: {}", title))?;
+        let mut rect = RECT::default();
+        GetClientRect(hwnd, &mut rect).map_err(|e| format!("读取窗口大小失败: {}", e))?;
+        let mut origin = POINT { x: 0, y: 0 };
+        if !ClientToScreen(hwnd, &mut origin).as_bool() {
+            return Err(format!("读取窗口位置失败: {}", title));
+        }
+        Ok((
+            origin.x,
+            origin.y,
+            (rect.right - rect.left) as u32,
+            (rect.bottom - rect.top) as u32,
+        ))
+    }
+}
+
+#[cfg(not(windows))]
+pub fn window_client_rect(title: &str) -> Result<(i32, i32, u32, u32), String> {
+    Err(format!("当前平台不支持窗口坐标: {}", title))
+}
@@ -0,0 +1,223 @@
+use std::{
+    collections::HashMap,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{channel, Sender},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use image::Rgb;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    global::{common::RGB, model::CaptureMode},
+    rgb::{source::Frame, template::frame_to_image},
+};
+
+const MATCHED: Rgb<u8> = Rgb([0, 255, 0]); // 匹配的坐标用绿色框标出
+const MISSED: Rgb<u8> = Rgb([255, 0, 255]); // 不匹配的坐标用紫色框标出
+const MARK_RADIUS: i32 = 3; // 标记框的半径, 框内的像素保持原样
+
+// 等待保存的调试截图: (目录, 名称, 截图, 标出的坐标, 限制)
+type CaptureJob = (PathBuf, String, Frame, Vec<(i32, i32, bool)>, CaptureConfig);
+
+lazy_static! {
+    static ref WRITER: Mutex<Option<Sender<CaptureJob>>> = Mutex::new(None); // 保存截图的线程
+}
+
+/// 用户配置中的调试截图限制
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptureConfig {
+    pub max_side: u32,    // 截图最大边长, 超过时以坐标为中心裁剪
+    pub max_files: usize, // 最多保留的截图数量, 超过时删除最早的
+    pub max_bytes: u64,   // 截图目录最大占用空间
+    pub interval: u64,    // always 方式同一节点两次截图的最短间隔ms
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        CaptureConfig {
+            max_side: 200,
+            max_files: 200,
+            max_bytes: 50 * 1024 * 1024,
+            interval: 1000,
+        }
+    }
+}
+
+/**
+ * 记录每个节点上一次的结果和截图时间, 判断是否需要保存截图
+ */
+#[derive(Debug, Default)]
+pub struct CaptureTracker {
+    nodes: HashMap<String, (bool, Option<Instant>)>, // 节点 -> (上一次结果, 上一次截图时间)
+}
+
+impl CaptureTracker {
+    /// 记录节点的判断结果, 返回是否需要保存截图
+    /// flip 方式第一次判断只记录结果, 之后结果变化时保存
+    pub fn record(
+        &mut self,
+        key: &str,
+        mode: CaptureMode,
+        matched: bool,
+        now: Instant,
+        interval: Duration,
+    ) -> bool {
+        let last = self.nodes.get(key).copied();
+        let capture = match mode {
+            CaptureMode::Off => false,
+            CaptureMode::Always => last
+                .and_then(|(_, at)| at)
+                .is_none_or(|at| now.saturating_duration_since(at) >= interval),
+            CaptureMode::Flip => last.is_some_and(|(result, _)| result != matched),
+        };
+        let captured_at = if capture {
+            Some(now)
+        } else {
+            last.and_then(|(_, at)| at)
+        };
+        self.nodes.insert(key.to_string(), (matched, captured_at));
+        capture
+    }
+}
+
+/// 以 (x, y) 为中心裁剪截图, 边长不超过 max_side, 靠近边缘时不超出截图
+pub fn crop_around(frame: &Frame, x: i32, y: i32, max_side: u32) -> Frame {
+    let width = frame.width.min(max_side.max(1));
+    let height = frame.height.min(max_side.max(1));
+    let clamp = |center: i32, origin: i32, size: u32, side: u32| {
+        (center - side as i32 / 2).clamp(origin, origin + (size - side) as i32)
+    };
+    let left = clamp(x, frame.x, frame.width, width);
+    let top = clamp(y, frame.y, frame.height, height);
+    let mut pixels = Vec::with_capacity((width * height) as usize);
+    for py in top..top + height as i32 {
+        for px in left..left + width as i32 {
+            // 裁剪区域在截图内, 一定能读取到
+            pixels.push(frame.pixel(px, py).unwrap_or(RGB::new(0, 0, 0)));
+        }
+    }
+    Frame::new(left, top, width, height, pixels)
+}
+
+/**
+ * 截图转换为图片, 在每个判断的坐标周围画框
+ * 匹配的坐标为绿色, 不匹配的为紫色, 坐标本身的像素不被覆盖
+ */
+pub fn annotate(frame: &Frame, points: &[(i32, i32, bool)]) -> image::RgbImage {
+    let mut image = frame_to_image(frame);
+    for (x, y, matched) in points {
+        let color = if *matched { MATCHED } else { MISSED };
+        for dy in -MARK_RADIUS..=MARK_RADIUS {
+            for dx in -MARK_RADIUS..=MARK_RADIUS {
+                if dx.abs() != MARK_RADIUS && dy.abs() != MARK_RADIUS {
+                    continue;
+                }
+                let px = x + dx - frame.x;
+                let py = y + dy - frame.y;
+                if px >= 0 && py >= 0 && (px as u32) < frame.width && (py as u32) < frame.height {
+                    image.put_pixel(px as u32, py as u32, color);
+                }
+            }
+        }
+    }
+    image
+}
+
+/**
+ * 删除多余的截图, 按修改时间保留最新的 max_files 张且总大小不超过 max_bytes
+ * 已经被删除的文件跳过, 返回删除的数量
+ */
+pub fn prune(dir: &Path, config: &CaptureConfig) -> Result<usize, String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("读取截图目录失败: {}", e))?;
+    let mut files: Vec<(PathBuf, std::time::SystemTime, u64)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "png"))
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            Some((entry.path(), meta.modified().ok()?, meta.len()))
+        })
+        .collect();
+    // 新的在前, 同一时间按文件名排序
+    files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| b.0.cmp(&a.0)));
+    let mut total = 0;
+    let mut removed = 0;
+    for (i, (path, _, size)) in files.iter().enumerate() {
+        total += size;
+        if i >= config.max_files || total > config.max_bytes {
+            match std::fs::remove_file(path) {
+                Ok(()) => removed += 1,
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("删除截图失败: {}", e)),
+            }
+        }
+    }
+    Ok(removed)
+}
+
+/**
+ * 保存调试截图, 以第一个坐标为中心裁剪并标出所有坐标, 保存后清理多余的截图
+ * 文件名为 时间_名称_结果.png, 例如 20250101-120000-123_100,200_y.png
+ */
+pub fn save_capture(
+    dir: &Path,
+    name: &str,
+    frame: &Frame,
+    points: &[(i32, i32, bool)],
+    config: &CaptureConfig,
+) -> Result<PathBuf, String> {
+    let (x, y, matched) = points
+        .first()
+        .copied()
+        .ok_or_else(|| "没有需要标出的坐标".to_string())?;
+    let frame = crop_around(frame, x, y, config.max_side);
+    std::fs::create_dir_all(dir).map_err(|e| format!("创建截图目录失败: {}", e))?;
+    let file = format!(
+        "{}_{}_{}.png",
+        chrono::Local::now().format("%Y%m%d-%H%M%S-%3f"),
+        name.replace(['/', '\\', '|', ':'], "_"),
+        if matched { "y" } else { "n" }
+    );
+    let path = dir.join(file);
+    annotate(&frame, points)
+        .save(&path)
+        .map_err(|e| format!("保存截图失败: {}", e))?;
+    prune(dir, config)?;
+    Ok(path)
+}
+
+/**
+ * 在后台保存调试截图, 所有截图由同一个线程依次保存和清理
+ * 避免多个线程同时清理目录
+ */
+pub fn queue_capture(
+    dir: PathBuf,
+    name: String,
+    frame: Frame,
+    points: Vec<(i32, i32, bool)>,
+    config: CaptureConfig,
+) {
+    let mut writer = WRITER.lock().unwrap();
+    let sender = writer.get_or_insert_with(|| {
+        let (sender, receiver) = channel::<CaptureJob>();
+        thread::spawn(move || {
+            for (dir, name, frame, points, config) in receiver {
+                if let Err(e) = save_capture(&dir, &name, &frame, &points, &config) {
+                    eprintln!("{}", e);
+                }
+            }
+        });
+        sender
+    });
+    if sender.send((dir, name, frame, points, config)).is_err() {
+        // 线程已退出, 下次重新创建
+        *writer = None;
+    }
+}
@@ -0,0 +1,287 @@
+use std::path::{Path, PathBuf};
+
+use image::{GrayImage, Luma};
+
+use crate::{
+    global::{
+        common::RGB,
+        model::{ColorMetric, Digits},
+    },
+    rgb::{check::parse_rgb, metric::color_matches, pattern::parse_region, source::Frame},
+    user::user::get_digits_dir,
+};
+
+const DIGITS: &str = "0123456789";
+
+/// 二值图, 数字颜色的像素为 true
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mask {
+    pub width: u32,
+    pub height: u32,
+    pub bits: Vec<bool>, // 按行存储
+}
+
+impl Mask {
+    /// 截图 region 区域内按颜色分割, 读取不到的像素算作背景
+    pub fn from_frame(
+        frame: &Frame,
+        region: (i32, i32, u32, u32),
+        rgb: RGB,
+        metric: ColorMetric,
+        tolerance: f64,
+        saturation: Option<f64>,
+    ) -> Mask {
+        let (x, y, width, height) = region;
+        let mut bits = Vec::with_capacity((width * height) as usize);
+        for dy in 0..height as i32 {
+            for dx in 0..width as i32 {
+                bits.push(
+                    frame
+                        .pixel(x + dx, y + dy)
+                        .is_some_and(|c| color_matches(metric, c, rgb, tolerance, saturation)),
+                );
+            }
+        }
+        Mask {
+            width,
+            height,
+            bits,
+        }
+    }
+
+    /// 字形图片, 亮度不低于一半的像素为 true
+    pub fn open(path: impl AsRef<Path>) -> Result<Mask, String> {
+        let path = path.as_ref();
+        let image = image::open(path)
+            .map_err(|e| format!("读取字形失败 {}: {}", path.display(), e))?
+            .to_luma8();
+        Ok(Mask {
+            width: image.width(),
+            height: image.height(),
+            bits: image.pixels().map(|p| p.0[0] >= 128).collect(),
+        })
+    }
+
+    /// 保存为黑底白字的 png
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let image = GrayImage::from_fn(self.width, self.height, |x, y| {
+            Luma([if self.get(x, y) { 255 } else { 0 }])
+        });
+        image.save(path).map_err(|e| format!("保存字形失败: {}", e))
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> bool {
+        x < self.width && y < self.height && self.bits[(y * self.width + x) as usize]
+    }
+
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Mask {
+        let mut bits = Vec::with_capacity((width * height) as usize);
+        for dy in 0..height {
+            for dx in 0..width {
+                bits.push(self.get(x + dx, y + dy));
+            }
+        }
+        Mask {
+            width,
+            height,
+            bits,
+        }
+    }
+
+    /// 去掉四周的背景, 没有前景像素时返回 None
+    pub fn trim(&self) -> Option<Mask> {
+        let column = |x: u32| (0..self.height).any(|y| self.get(x, y));
+        let row = |y: u32| (0..self.width).any(|x| self.get(x, y));
+        let left = (0..self.width).find(|x| column(*x))?;
+        let right = (0..self.width).rev().find(|x| column(*x))?;
+        let top = (0..self.height).find(|y| row(*y))?;
+        let bottom = (0..self.height).rev().find(|y| row(*y))?;
+        Some(self.crop(left, top, right - left + 1, bottom - top + 1))
+    }
+
+    /// 按空白列切分成单个字形, 从左到右排列, 每个字形去掉四周的背景
+    pub fn segment(&self) -> Vec<Mask> {
+        let mut glyphs = Vec::new();
+        let mut start = None;
+        for x in 0..=self.width {
+            let filled = x < self.width && (0..self.height).any(|y| self.get(x, y));
+            match (filled, start) {
+                (true, None) => start = Some(x),
+                (false, Some(s)) => {
+                    glyphs.extend(self.crop(s, 0, x - s, self.height).trim());
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        glyphs
+    }
+}
+
+/**
+ * 两个字形的相似度 0-1
+ * 模板按字形大小缩放后逐像素比较, 再按宽高比的差异打折, 避免窄的 1 被拉伸后和其他数字相似
+ */
+pub fn glyph_similarity(glyph: &Mask, template: &Mask) -> f64 {
+    if glyph.bits.is_empty() || template.bits.is_empty() {
+        return 0.0;
+    }
+    let mut same = 0;
+    for y in 0..glyph.height {
+        for x in 0..glyph.width {
+            let tx = x * template.width / glyph.width;
+            let ty = y * template.height / glyph.height;
+            if glyph.get(x, y) == template.get(tx, ty) {
+                same += 1;
+            }
+        }
+    }
+    let ratio = |m: &Mask| m.width as f64 / m.height as f64;
+    let (a, b) = (ratio(glyph), ratio(template));
+    same as f64 / glyph.bits.len() as f64 * a.min(b) / a.max(b)
+}
+
+/// 字形集名称对应的目录, 名称不能包含路径
+pub fn glyph_set_path(name: &str) -> Result<PathBuf, String> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+        return Err(format!("字形集名称有误: {}", name));
+    }
+    let dir = get_digits_dir().ok_or_else(|| "找不到配置目录".to_string())?;
+    Ok(dir.join(name))
+}
+
+/**
+ * 一套数字字形, 目录下的 0.png 到 9.png, 缺少的数字无法识别
+ */
+#[derive(Debug, Clone)]
+pub struct GlyphSet {
+    pub glyphs: Vec<(char, Mask)>,
+}
+
+impl GlyphSet {
+    pub fn open(dir: impl AsRef<Path>) -> Result<GlyphSet, String> {
+        let dir = dir.as_ref();
+        let mut glyphs = Vec::new();
+        for digit in DIGITS.chars() {
+            let path = dir.join(format!("{}.png", digit));
+            if path.exists() {
+                let mask = Mask::open(&path)?
+                    .trim()
+                    .ok_or_else(|| format!("字形是空的: {}", path.display()))?;
+                glyphs.push((digit, mask));
+            }
+        }
+        if glyphs.is_empty() {
+            return Err(format!("字形集没有字形: {}", dir.display()));
+        }
+        Ok(GlyphSet { glyphs })
+    }
+
+    /// 读取配置目录中的字形集
+    pub fn load(name: &str) -> Result<GlyphSet, String> {
+        GlyphSet::open(glyph_set_path(name)?)
+    }
+
+    /// 最相似的数字, 相似度低于 min_score 时返回 None
+    pub fn recognize(&self, glyph: &Mask, min_score: f64) -> Option<char> {
+        self.glyphs
+            .iter()
+            .map(|(digit, template)| (*digit, glyph_similarity(glyph, template)))
+            .filter(|(_, score)| *score >= min_score)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(digit, _)| digit)
+    }
+
+    /// 识别区域内的数字, 没有数字或有无法识别的字形时返回 None
+    pub fn read(&self, mask: &Mask, min_score: f64) -> Option<i64> {
+        let text = mask
+            .segment()
+            .iter()
+            .map(|glyph| self.recognize(glyph, min_score))
+            .collect::<Option<String>>()?;
+        text.parse().ok()
+    }
+}
+
+/**
+ * 从截图中保存字形, text 为区域内从左到右显示的数字, 例如 "0123456789"
+ * 分割出的字形数量需要和 text 一致, 返回保存的文件
+ */
+pub fn save_glyphs(dir: impl AsRef<Path>, text: &str, mask: &Mask) -> Result<Vec<PathBuf>, String> {
+    let dir = dir.as_ref();
+    if let Some(c) = text.chars().find(|c| !c.is_ascii_digit()) {
+        return Err(format!("只支持数字字形: {}", c));
+    }
+    let glyphs = mask.segment();
+    if glyphs.len() != text.chars().count() {
+        return Err(format!(
+            "区域内分割出 {} 个字形, 和 {} 不一致",
+            glyphs.len(),
+            text
+        ));
+    }
+    std::fs::create_dir_all(dir).map_err(|e| format!("创建字形目录失败: {}", e))?;
+    let mut paths = Vec::new();
+    for (digit, glyph) in text.chars().zip(glyphs) {
+        let path = dir.join(format!("{}.png", digit));
+        glyph.save(&path)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+/**
+ * 数字节点的配置, 字形集单独读取以便缓存
+ */
+#[derive(Debug, Clone)]
+pub struct DigitReader {
+    pub region: (i32, i32, u32, u32),
+    pub rgb: RGB,
+    pub tolerance: f64,
+    pub metric: ColorMetric,
+    pub saturation: Option<f64>,
+    pub min_score: f64,
+}
+
+impl DigitReader {
+    pub fn parse(d: &Digits) -> Result<DigitReader, String> {
+        let region = parse_region(&d.region).ok_or_else(|| format!("区域有误: {}", d.region))?;
+        let rgb = parse_rgb(&d.rgb).ok_or_else(|| format!("颜色有误: {}", d.rgb))?;
+        Ok(DigitReader {
+            region,
+            rgb,
+            tolerance: d.tolerance,
+            metric: d.metric,
+            saturation: d.saturation,
+            min_score: d.min_score.unwrap_or(0.8),
+        })
+    }
+
+    /// 区域左上角和右下角, 用于计算截图范围
+    pub fn corners(&self) -> [(i32, i32); 2] {
+        let (x, y, width, height) = self.region;
+        [(x, y), (x + width as i32 - 1, y + height as i32 - 1)]
+    }
+
+    pub fn mask(&self, frame: &Frame) -> Mask {
+        Mask::from_frame(
+            frame,
+            self.region,
+            self.rgb,
+            self.metric,
+            self.tolerance,
+            self.saturation,
+        )
+    }
+
+    pub fn read(&self, frame: &Frame, glyphs: &GlyphSet) -> Option<i64> {
+        glyphs.read(&self.mask(frame), self.min_score)
+    }
+}
+
+/// 数字是否满足 below/above/equal, 设置了多个时需要同时满足, 都没有设置时读取到数字即成立
+pub fn number_holds(value: i64, d: &Digits) -> bool {
+    d.below.is_none_or(|below| value < below)
+        && d.above.is_none_or(|above| value > above)
+        && d.equal.is_none_or(|equal| value == equal)
+}
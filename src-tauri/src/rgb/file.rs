@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use image::RgbImage;
+
+use crate::{
+    global::common::RGB,
+    rgb::{
+        monitor::Monitor,
+        source::{Frame, ScreenSource},
+    },
+};
+
+/**
+ * 从截图文件读取画面, 用于在没有游戏窗口时检查取色条件
+ * 图片左上角默认对应屏幕坐标 (0, 0)
+ */
+pub struct ImageSource {
+    image: RgbImage,
+    origin: (i32, i32), // 图片左上角的屏幕坐标
+}
+
+impl ImageSource {
+    pub fn open(path: impl AsRef<Path>) -> Result<ImageSource, String> {
+        let path = path.as_ref();
+        let image = image::open(path)
+            .map_err(|e| format!("读取截图失败 {}: {}", path.display(), e))?
+            .to_rgb8();
+        Ok(ImageSource::from_image(image))
+    }
+
+    /// 使用内存中的图片, 左上角对应屏幕坐标 (0, 0)
+    pub fn from_image(image: RgbImage) -> ImageSource {
+        ImageSource {
+            image,
+            origin: (0, 0),
+        }
+    }
+
+    /// 图片左上角的屏幕坐标, 用于多个显示器的虚拟桌面截图
+    pub fn with_origin(mut self, x: i32, y: i32) -> ImageSource {
+        self.origin = (x, y);
+        self
+    }
+}
+
+impl ScreenSource for ImageSource {
+    fn region(&self, x: i32, y: i32, width: u32, height: u32) -> Result<Frame, String> {
+        // 换算为图片中的坐标
+        let (ix, iy) = (
+            x as i64 - self.origin.0 as i64,
+            y as i64 - self.origin.1 as i64,
+        );
+        if ix < 0
+            || iy < 0
+            || ix + width as i64 > self.image.width() as i64
+            || iy + height as i64 > self.image.height() as i64
+        {
+            return Err(format!(
+                "区域超出截图范围: {},{} {}x{}",
+                x, y, width, height
+            ));
+        }
+        let mut pixels = Vec::with_capacity(width as usize * height as usize);
+        for py in iy as u32..iy as u32 + height {
+            for px in ix as u32..ix as u32 + width {
+                let [r, g, b] = self.image.get_pixel(px, py).0;
+                pixels.push(RGB::new(r, g, b));
+            }
+        }
+        Ok(Frame::new(x, y, width, height, pixels))
+    }
+
+    fn size(&self) -> Result<(u32, u32), String> {
+        Ok(self.image.dimensions())
+    }
+
+    fn monitors(&self) -> Result<Vec<Monitor>, String> {
+        let (width, height) = self.image.dimensions();
+        Ok(vec![Monitor {
+            index: 1,
+            name: "image".to_string(),
+            x: self.origin.0,
+            y: self.origin.1,
+            width,
+            height,
+            primary: true,
+        }])
+    }
+}
@@ -0,0 +1,162 @@
+use windows::core::BOOL;
+use windows::Win32::Foundation::{HWND, LPARAM, RECT};
+use windows::Win32::Graphics::Gdi::{
+    BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject,
+    EnumDisplayMonitors, GetDC, GetDIBits, GetMonitorInfoW, ReleaseDC, SelectObject, BITMAPINFO,
+    BITMAPINFOHEADER, DIB_RGB_COLORS, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW,
+    MONITORINFOF_PRIMARY, SRCCOPY,
+};
+use windows::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_CXSCREEN, SM_CYSCREEN};
+
+use crate::{
+    global::{common::RGB, init::global_hdc_screen},
+    rgb::{
+        monitor::{sort_monitors, Monitor},
+        source::{Frame, ScreenSource},
+    },
+};
+
+/**
+ * 通过 GDI 读取屏幕画面
+ * 规则开启后使用全局屏幕句柄, 未开启时临时获取
+ * 屏幕句柄覆盖整个虚拟桌面, 其他显示器上的负数坐标也可以直接截取
+ */
+pub struct GdiSource;
+
+impl ScreenSource for GdiSource {
+    fn region(&self, x: i32, y: i32, width: u32, height: u32) -> Result<Frame, String> {
+        unsafe {
+            let global = global_hdc_screen();
+            let hdc_screen = match global {
+                Some(hdc_raw) => HDC(hdc_raw as *mut std::ffi::c_void),
+                None => {
+                    let hdc = GetDC(Some(HWND(std::ptr::null_mut())));
+                    if hdc.is_invalid() {
+                        return Err("获取屏幕句柄失败".to_string());
+                    }
+                    hdc
+                }
+            };
+
+            let hdc_mem = CreateCompatibleDC(Some(hdc_screen));
+            let hbm = CreateCompatibleBitmap(hdc_screen, width as i32, height as i32);
+            SelectObject(hdc_mem, hbm.into());
+
+            let _ = BitBlt(
+                hdc_mem,
+                0,
+                0,
+                width as i32,
+                height as i32,
+                Some(hdc_screen),
+                x,
+                y,
+                SRCCOPY,
+            );
+
+            let mut bmi = BITMAPINFO {
+                bmiHeader: BITMAPINFOHEADER {
+                    biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                    biWidth: width as i32,
+                    biHeight: -(height as i32), // 负数表示从上到下存储
+                    biPlanes: 1,
+                    biBitCount: 24,
+                    biCompression: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            // 每行按4字节对齐
+            let stride = (width as usize * 3).div_ceil(4) * 4;
+            let mut data = vec![0u8; stride * height as usize];
+            let lines = GetDIBits(
+                hdc_mem,
+                hbm,
+                0,
+                height,
+                Some(data.as_mut_ptr() as *mut _),
+                &mut bmi,
+                DIB_RGB_COLORS,
+            );
+
+            let _ = DeleteObject(hbm.into());
+            let _ = DeleteDC(hdc_mem);
+            if global.is_none() {
+                ReleaseDC(Some(HWND(std::ptr::null_mut())), hdc_screen);
+            }
+            if lines == 0 {
+                return Err(format!("截图失败: {},{} {}x{}", x, y, width, height));
+            }
+
+            // 注意：像素顺序是 BGR
+            let mut pixels = Vec::with_capacity(width as usize * height as usize);
+            for row in data.chunks(stride) {
+                for p in row[..width as usize * 3].chunks(3) {
+                    pixels.push(RGB::new(p[2], p[1], p[0]));
+                }
+            }
+            Ok(Frame::new(x, y, width, height, pixels))
+        }
+    }
+
+    fn size(&self) -> Result<(u32, u32), String> {
+        unsafe {
+            Ok((
+                GetSystemMetrics(SM_CXSCREEN) as u32,
+                GetSystemMetrics(SM_CYSCREEN) as u32,
+            ))
+        }
+    }
+
+    fn monitors(&self) -> Result<Vec<Monitor>, String> {
+        let mut monitors: Vec<Monitor> = Vec::new();
+        unsafe {
+            let ok = EnumDisplayMonitors(
+                None,
+                None,
+                Some(enum_monitor),
+                LPARAM(&mut monitors as *mut Vec<Monitor> as isize),
+            );
+            if !ok.as_bool() {
+                return Err("枚举显示器失败".to_string());
+            }
+        }
+        Ok(sort_monitors(monitors))
+    }
+}
+
+// EnumDisplayMonitors 的回调, data 为 Vec<Monitor> 的指针
+unsafe extern "system" fn enum_monitor(
+    hmonitor: HMONITOR,
+    _hdc: HDC,
+    _rect: *mut RECT,
+    data: LPARAM,
+) -> BOOL {
+    let monitors = &mut *(data.0 as *mut Vec<Monitor>);
+    let mut info = MONITORINFOEXW {
+        monitorInfo: MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFOEXW>() as u32,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    if GetMonitorInfoW(hmonitor, &mut info.monitorInfo).as_bool() {
+        let rect = info.monitorInfo.rcMonitor;
+        let len = info
+            .szDevice
+            .iter()
+            .position(|c| *c == 0)
+            .unwrap_or(info.szDevice.len());
+        monitors.push(Monitor {
+            index: 0,
+            name: String::from_utf16_lossy(&info.szDevice[..len]),
+            x: rect.left,
+            y: rect.top,
+            width: (rect.right - rect.left) as u32,
+            height: (rect.bottom - rect.top) as u32,
+            primary: info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0,
+        });
+    }
+    // 返回 true 继续枚举
+    BOOL(1)
+}
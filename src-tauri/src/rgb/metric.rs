@@ -0,0 +1,225 @@
+use crate::global::{common::RGB, model::ColorMetric};
+
+// 饱和度低于该值时视为灰色, 色相没有意义
+const GREY_SATURATION: f64 = 0.1;
+// hsv 方式没有设置 saturation 时允许的饱和度偏差
+const DEFAULT_SATURATION: f64 = 0.2;
+
+/// 采样得到的颜色和建议的偏差
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToleranceSuggestion {
+    pub rgb: RGB,                // 采样的平均颜色
+    pub tolerance: f64,          // 建议的偏差
+    pub saturation: Option<f64>, // hsv 方式建议的饱和度偏差
+}
+
+/// rgb 转换为 hsv, 色相 0-360, 饱和度和亮度 0-1
+pub fn rgb_to_hsv(rgb: RGB) -> (f64, f64, f64) {
+    let r = rgb.r as f64 / 255.0;
+    let g = rgb.g as f64 / 255.0;
+    let b = rgb.b as f64 / 255.0;
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    let saturation = if max == 0.0 { 0.0 } else { delta / max };
+    (hue, saturation, max)
+}
+
+/// rgb 转换为 CIE Lab (D65)
+pub fn rgb_to_lab(rgb: RGB) -> (f64, f64, f64) {
+    let linear = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let (r, g, b) = (linear(rgb.r), linear(rgb.g), linear(rgb.b));
+    let x = (0.4124564 * r + 0.3575761 * g + 0.1804375 * b) / 0.95047;
+    let y = 0.2126729 * r + 0.7151522 * g + 0.0721750 * b;
+    let z = (0.0193339 * r + 0.1191920 * g + 0.9503041 * b) / 1.08883;
+    let f = |t: f64| {
+        if t > 0.008856 {
+            t.cbrt()
+        } else {
+            7.787 * t + 16.0 / 116.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
+/**
+ * 两个 Lab 颜色的 CIEDE2000 色差
+ */
+pub fn ciede2000(lab1: (f64, f64, f64), lab2: (f64, f64, f64)) -> f64 {
+    let (l1, a1, b1) = lab1;
+    let (l2, a2, b2) = lab2;
+    let pow7 = |c: f64| c.powi(7);
+    let c_bar = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let g = 0.5 * (1.0 - (pow7(c_bar) / (pow7(c_bar) + pow7(25.0))).sqrt());
+    let (a1, a2) = ((1.0 + g) * a1, (1.0 + g) * a2);
+    let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+    let hue = |a: f64, b: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let (h1, h2) = (hue(a1, b1), hue(a2, b2));
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let dh = 2.0 * (c1 * c2).sqrt() * (dh / 2.0).to_radians().sin();
+
+    let l_bar = (l1 + l2) / 2.0;
+    let c_bar = (c1 + c2) / 2.0;
+    let h_bar = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+    let cos = |deg: f64| deg.to_radians().cos();
+    let t =
+        1.0 - 0.17 * cos(h_bar - 30.0) + 0.24 * cos(2.0 * h_bar) + 0.32 * cos(3.0 * h_bar + 6.0)
+            - 0.20 * cos(4.0 * h_bar - 63.0);
+    let d_theta = 30.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp();
+    let rc = 2.0 * (pow7(c_bar) / (pow7(c_bar) + pow7(25.0))).sqrt();
+    let sl = 1.0 + 0.015 * (l_bar - 50.0).powi(2) / (20.0 + (l_bar - 50.0).powi(2)).sqrt();
+    let sc = 1.0 + 0.045 * c_bar;
+    let sh = 1.0 + 0.015 * c_bar * t;
+    let rt = -(2.0 * d_theta).to_radians().sin() * rc;
+
+    let (dl, dc, dh) = (dl / sl, dc / sc, dh / sh);
+    (dl * dl + dc * dc + dh * dh + rt * dc * dh).sqrt()
+}
+
+// 色相差值 0-180, 目标颜色接近灰色时不比较色相
+fn hue_distance(color: RGB, target: RGB) -> f64 {
+    let (h1, _, _) = rgb_to_hsv(color);
+    let (h2, s2, _) = rgb_to_hsv(target);
+    if s2 < GREY_SATURATION {
+        return 0.0;
+    }
+    let d = (h1 - h2).abs();
+    d.min(360.0 - d)
+}
+
+fn saturation_distance(color: RGB, target: RGB) -> f64 {
+    (rgb_to_hsv(color).1 - rgb_to_hsv(target).1).abs()
+}
+
+/**
+ * 按 metric 计算两个颜色的差异, hsv 方式只返回色相差值
+ */
+pub fn color_distance(metric: ColorMetric, color: RGB, target: RGB) -> f64 {
+    match metric {
+        ColorMetric::ChannelMax => {
+            let dr = (color.r as i32 - target.r as i32).abs();
+            let dg = (color.g as i32 - target.g as i32).abs();
+            let db = (color.b as i32 - target.b as i32).abs();
+            dr.max(dg).max(db) as f64
+        }
+        ColorMetric::Euclidean => (color.squared_distance_to(&target) as f64).sqrt(),
+        ColorMetric::Hsv => hue_distance(color, target),
+        ColorMetric::Ciede2000 => ciede2000(rgb_to_lab(color), rgb_to_lab(target)),
+    }
+}
+
+/**
+ * 颜色差异是否在偏差范围内
+ * hsv 方式同时要求饱和度差值不超过 saturation, 为空时使用默认值
+ */
+pub fn color_matches(
+    metric: ColorMetric,
+    color: RGB,
+    target: RGB,
+    tolerance: f64,
+    saturation: Option<f64>,
+) -> bool {
+    if color_distance(metric, color, target) > tolerance {
+        return false;
+    }
+    match metric {
+        ColorMetric::Hsv => {
+            saturation_distance(color, target) <= saturation.unwrap_or(DEFAULT_SATURATION)
+        }
+        _ => true,
+    }
+}
+
+// 最大值和 平均值 + 3 倍标准差 中较大的一个, 覆盖采样中没有出现的波动
+fn spread(values: &[f64]) -> f64 {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    let max = values.iter().cloned().fold(0.0, f64::max);
+    max.max(mean + 3.0 * variance.sqrt())
+}
+
+fn round_up(value: f64, step: f64) -> f64 {
+    // 去掉浮点误差后再向上取整
+    ((value / step - 1e-9).ceil() * step).max(0.0)
+}
+
+/**
+ * 根据同一个坐标多次采样的颜色建议偏差
+ * 以平均颜色为目标, 偏差覆盖采样到的波动, 例如画面闪烁或半透明特效
+ */
+pub fn suggest_tolerance(metric: ColorMetric, samples: &[RGB]) -> Option<ToleranceSuggestion> {
+    if samples.is_empty() {
+        return None;
+    }
+    let n = samples.len() as f64;
+    let mean =
+        |c: fn(&RGB) -> u8| (samples.iter().map(|s| c(s) as f64).sum::<f64>() / n).round() as u8;
+    let rgb = RGB::new(mean(|s| s.r), mean(|s| s.g), mean(|s| s.b));
+
+    let distances: Vec<f64> = samples
+        .iter()
+        .map(|s| color_distance(metric, *s, rgb))
+        .collect();
+    let step = match metric {
+        ColorMetric::ChannelMax => 1.0,
+        _ => 0.1,
+    };
+    let saturation = match metric {
+        ColorMetric::Hsv => {
+            let d: Vec<f64> = samples
+                .iter()
+                .map(|s| saturation_distance(*s, rgb))
+                .collect();
+            Some(round_up(spread(&d), 0.01))
+        }
+        _ => None,
+    };
+    Some(ToleranceSuggestion {
+        rgb,
+        tolerance: round_up(spread(&distances), step),
+        saturation,
+    })
+}
@@ -1,2 +1,18 @@
 pub mod pick;
-pub mod check;
\ No newline at end of file
+pub mod bar;
+pub mod capture;
+pub mod check;
+pub mod coord;
+pub mod debug;
+pub mod digits;
+pub mod file;
+#[cfg(windows)]
+pub mod gdi;
+pub mod metric;
+pub mod monitor;
+pub mod pattern;
+pub mod probe;
+pub mod source;
+pub mod template;
+#[cfg(target_os = "linux")]
+pub mod x11;
@@ -0,0 +1,84 @@
+use serde::Serialize;
+
+/**
+ * 显示器在虚拟桌面上的位置
+ * 主显示器左上角为 (0, 0), 在主显示器左边或上边的显示器坐标为负数
+ */
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Monitor {
+    pub index: usize, // 从 1 开始, 按从左到右、从上到下排序
+    pub name: String, // 系统中的名称, 例如 \\.\DISPLAY2 或 HDMI-1
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub primary: bool, // 是否为主显示器
+}
+
+impl Monitor {
+    /// 区域 (x, y, 宽, 高)
+    pub fn rect(&self) -> (i32, i32, u32, u32) {
+        (self.x, self.y, self.width, self.height)
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x
+            && y >= self.y
+            && ((x - self.x) as u32) < self.width
+            && ((y - self.y) as u32) < self.height
+    }
+}
+
+/// 按位置排序并重新编号, 系统枚举的顺序不固定
+pub fn sort_monitors(mut monitors: Vec<Monitor>) -> Vec<Monitor> {
+    monitors.sort_by_key(|m| (m.x, m.y));
+    for (i, m) in monitors.iter_mut().enumerate() {
+        m.index = i + 1;
+    }
+    monitors
+}
+
+/**
+ * 把显示器位置换算为以主显示器左上角为原点, 返回原来的主显示器位置
+ * 用于 X11 等以根窗口左上角为原点的系统, 没有主显示器时不换算
+ */
+pub fn relative_to_primary(monitors: &mut [Monitor]) -> (i32, i32) {
+    let origin = match monitors.iter().find(|m| m.primary) {
+        Some(primary) => (primary.x, primary.y),
+        None => return (0, 0),
+    };
+    for m in monitors.iter_mut() {
+        m.x -= origin.0;
+        m.y -= origin.1;
+    }
+    origin
+}
+
+/**
+ * 按名称查找显示器
+ * name: primary 为主显示器, 数字为编号, 其他为系统中的名称
+ */
+pub fn find_monitor<'a>(monitors: &'a [Monitor], name: &str) -> Option<&'a Monitor> {
+    let name = name.trim();
+    if name == "primary" {
+        return monitors.iter().find(|m| m.primary);
+    }
+    if let Ok(index) = name.parse::<usize>() {
+        return monitors.iter().find(|m| m.index == index);
+    }
+    monitors.iter().find(|m| m.name == name)
+}
+
+/// 坐标所在的显示器
+pub fn monitor_at(monitors: &[Monitor], x: i32, y: i32) -> Option<&Monitor> {
+    monitors.iter().find(|m| m.contains(x, y))
+}
+
+/// 包含所有显示器的虚拟桌面区域 (x, y, 宽, 高)
+pub fn virtual_bounds(monitors: &[Monitor]) -> Option<(i32, i32, u32, u32)> {
+    let left = monitors.iter().map(|m| m.x).min()?;
+    let top = monitors.iter().map(|m| m.y).min()?;
+    let right = monitors.iter().map(|m| m.x + m.width as i32).max()?;
+    let bottom = monitors.iter().map(|m| m.y + m.height as i32).max()?;
+    Some((left, top, (right - left) as u32, (bottom - top) as u32))
+}
@@ -0,0 +1,112 @@
+use crate::{
+    global::{common::RGB, model::ColorPattern},
+    rgb::check::{color_check_one_by_one, parse_coordinate, parse_rgb},
+};
+
+/**
+ * 多点取色, 锚点加上一组相对偏移的取色点
+ * 匹配的点数达到 min_match 才算成立, 避免单个像素被相近颜色误判
+ */
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    pub points: Vec<(i32, i32, RGB, i32)>, // 偏移 dx, dy, 颜色, 允许的偏差
+    pub min_match: usize,
+}
+
+/// 解析 "x,y,宽,高" 格式的区域
+pub fn parse_region(region: &str) -> Option<(i32, i32, u32, u32)> {
+    let v: Vec<i32> = region
+        .split(",")
+        .map(|s| s.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    match v[..] {
+        [x, y, width, height] if width > 0 && height > 0 => {
+            Some((x, y, width as u32, height as u32))
+        }
+        _ => None,
+    }
+}
+
+impl Pattern {
+    pub fn parse(pattern: &ColorPattern) -> Result<Pattern, String> {
+        if pattern.points.is_empty() {
+            return Err("多点取色至少需要一个取色点".to_string());
+        }
+        let mut points = Vec::new();
+        for p in &pattern.points {
+            let rgb = parse_rgb(&p.rgb).ok_or_else(|| format!("颜色有误: {}", p.rgb))?;
+            points.push((p.dx, p.dy, rgb, p.tolerance));
+        }
+        let min_match = pattern
+            .min_match
+            .unwrap_or(points.len())
+            .clamp(1, points.len());
+        Ok(Pattern { points, min_match })
+    }
+
+    /// 取色点相对锚点的范围 (最小dx, 最小dy, 最大dx, 最大dy)
+    pub fn extent(&self) -> (i32, i32, i32, i32) {
+        let min_dx = self.points.iter().map(|p| p.0).min().unwrap_or(0);
+        let min_dy = self.points.iter().map(|p| p.1).min().unwrap_or(0);
+        let max_dx = self.points.iter().map(|p| p.0).max().unwrap_or(0);
+        let max_dy = self.points.iter().map(|p| p.1).max().unwrap_or(0);
+        (min_dx, min_dy, max_dx, max_dy)
+    }
+
+    /// 锚点在 (x, y) 时是否匹配, pixel 读取不到的点算作不匹配
+    pub fn matches_at(&self, x: i32, y: i32, pixel: impl Fn(i32, i32) -> Option<RGB>) -> bool {
+        let mut matched = 0;
+        for (i, (dx, dy, rgb, tolerance)) in self.points.iter().enumerate() {
+            if let Some(color) = pixel(x + dx, y + dy) {
+                if color_check_one_by_one(color, *rgb, *tolerance) {
+                    matched += 1;
+                }
+            }
+            if matched >= self.min_match {
+                return true;
+            }
+            // 剩下的点全部匹配也不够时提前结束
+            if matched + self.points.len() - i - 1 < self.min_match {
+                return false;
+            }
+        }
+        false
+    }
+
+    /// 在区域内逐行查找锚点位置, 返回第一个匹配的位置
+    pub fn find(
+        &self,
+        region: (i32, i32, u32, u32),
+        pixel: impl Fn(i32, i32) -> Option<RGB>,
+    ) -> Option<(i32, i32)> {
+        let (x, y, width, height) = region;
+        for ay in y..y + height as i32 {
+            for ax in x..x + width as i32 {
+                if self.matches_at(ax, ay, &pixel) {
+                    return Some((ax, ay));
+                }
+            }
+        }
+        None
+    }
+
+    /// 需要读取的坐标范围的两个角, 用于确定截图区域
+    pub fn corners(&self, pattern: &ColorPattern) -> Vec<(i32, i32)> {
+        let (min_dx, min_dy, max_dx, max_dy) = self.extent();
+        let anchors = match pattern.search.as_deref().and_then(parse_region) {
+            Some((x, y, width, height)) => {
+                vec![(x, y), (x + width as i32 - 1, y + height as i32 - 1)]
+            }
+            None => pattern
+                .anchor
+                .as_deref()
+                .and_then(parse_coordinate)
+                .into_iter()
+                .collect(),
+        };
+        anchors
+            .iter()
+            .flat_map(|(x, y)| [(x + min_dx, y + min_dy), (x + max_dx, y + max_dy)])
+            .collect()
+    }
+}
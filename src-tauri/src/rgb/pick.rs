@@ -1,79 +1,338 @@
-use device_query::{DeviceQuery, DeviceState, Keycode};
-use windows::Win32::Foundation::{HWND, POINT};
-use windows::Win32::Graphics::Gdi::{
-    BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDC, GetDIBits,
-     SelectObject, BITMAPINFO, BITMAPINFOHEADER, DIB_RGB_COLORS, SRCCOPY
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::RecvTimeoutError,
+    },
+    thread,
+    time::{Duration, Instant},
 };
-use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
 
-use crate::global::common::RGB;
+use serde::{Deserialize, Serialize};
 
-// 获取指定点位的rgb值
-pub fn pick_up_rgb(x: i32, y: i32) -> RGB {
-    unsafe {
-        let hdc_screen = {
-            let hdc = GetDC(Some(HWND(std::ptr::null_mut())));
-            if hdc.is_invalid() {
-                return RGB::new(0, 0, 0);
-            }
-            hdc
-        };
-
-        let hdc_mem = CreateCompatibleDC(Some(hdc_screen));
-        let hbm = CreateCompatibleBitmap(hdc_screen, 1, 1);
-        SelectObject(hdc_mem, hbm.into());
-
-        let _ = BitBlt(hdc_mem, 0, 0, 1, 1, Some(hdc_screen), x, y, SRCCOPY);
-
-        let mut bmi = BITMAPINFO {
-            bmiHeader: BITMAPINFOHEADER {
-                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
-                biWidth: 1,
-                biHeight: 1,
-                biPlanes: 1,
-                biBitCount: 24,
-                biCompression: 0,
-                ..Default::default()
+use crate::{
+    global::{common::RGB, model::ColorMetric},
+    input::hook::{cursor_position, is_held, subscribe, InputEvent, Subscription},
+    rgb::{
+        check::{get_coordinate_rgb, parse_coordinate},
+        metric::suggest_tolerance,
+        source::{screen, Frame, ScreenSource},
+    },
+    start::hotkey::HotKey,
+};
+
+const ESC: u32 = 27; // 取消取色
+const CTRL: u32 = 17; // 默认也可以用 Ctrl 确认取色, 输入钩子不区分左右
+
+static PICKING: AtomicBool = AtomicBool::new(false); // 是否正在取色
+static CANCEL: AtomicBool = AtomicBool::new(false); // 取消当前取色
+
+/// 取色设置, 为空的项使用默认值
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct PickOptions {
+    pub hotkey: Option<String>, // 确认取色的快捷键, 默认 F1 或 Ctrl
+    pub timeout: Option<u64>,   // 超时时间ms, 默认 30000
+    pub size: Option<u32>,      // 统计颜色的邻域大小 N×N, 默认 5
+    pub interval: Option<u64>,  // 推送鼠标位置和颜色的间隔ms, 默认 50
+}
+
+/// 取色过程中推送的鼠标位置和颜色
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PickUpdate {
+    pub x: i32,
+    pub y: i32,
+    pub rgb: String, // "r,g,b"
+    pub hex: String, // "#rrggbb"
+}
+
+/// 取色结果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PickResult {
+    pub coordinate: String, // "x,y"
+    pub rgb: String,        // 鼠标位置的颜色 "r,g,b"
+    pub hex: String,        // "#rrggbb"
+    pub size: u32,          // 实际统计的邻域大小
+    pub mean: [f64; 3],     // 邻域内红绿蓝的平均值
+    pub variance: [f64; 3], // 邻域内红绿蓝的方差
+}
+
+/// 取色结束的方式
+#[derive(Debug, Clone, PartialEq)]
+pub enum PickOutcome {
+    Picked(PickResult),
+    Cancelled, // 按下 Esc 或调用 cancel_pick
+    TimedOut,
+}
+
+/// 采样取色结果
+#[derive(Debug, Serialize)]
+pub struct ColorSample {
+    pub coordinate: String,      // 鼠标坐标
+    pub rgb: String,             // 采样的平均颜色
+    pub tolerance: f64,          // 建议的偏差
+    pub metric: ColorMetric,     // 偏差对应的计算方式
+    pub saturation: Option<f64>, // hsv 方式建议的饱和度偏差
+}
+
+pub fn hex(rgb: RGB) -> String {
+    format!("#{:02x}{:02x}{:02x}", rgb.r, rgb.g, rgb.b)
+}
+
+/// 区域内红绿蓝的平均值和方差
+pub fn color_stats(frame: &Frame) -> ([f64; 3], [f64; 3]) {
+    let n = frame.pixels.len().max(1) as f64;
+    let channels = |p: &RGB| [p.r as f64, p.g as f64, p.b as f64];
+    let mut mean = [0.0; 3];
+    for p in &frame.pixels {
+        for (m, c) in mean.iter_mut().zip(channels(p)) {
+            *m += c;
+        }
+    }
+    mean.iter_mut().for_each(|m| *m /= n);
+    let mut variance = [0.0; 3];
+    for p in &frame.pixels {
+        for ((v, m), c) in variance.iter_mut().zip(mean).zip(channels(p)) {
+            *v += (c - m).powi(2);
+        }
+    }
+    variance.iter_mut().for_each(|v| *v /= n);
+    (mean, variance)
+}
+
+/**
+ * 读取 (x, y) 的颜色和以它为中心的 size×size 邻域
+ * size 为偶数时加 1, 邻域超出屏幕时只统计该像素
+ */
+pub fn pick_at(source: &dyn ScreenSource, x: i32, y: i32, size: u32) -> Result<PickResult, String> {
+    let size = size.max(1) | 1;
+    let r = (size / 2) as i32;
+    let (frame, size) = match source.region(x - r, y - r, size, size) {
+        Ok(frame) => (frame, size),
+        Err(_) => (source.region(x, y, 1, 1)?, 1),
+    };
+    let rgb = frame
+        .pixel(x, y)
+        .ok_or_else(|| format!("读取像素失败: {},{}", x, y))?;
+    let (mean, variance) = color_stats(&frame);
+    Ok(PickResult {
+        coordinate: format!("{},{}", x, y),
+        rgb: format!("{},{},{}", rgb.r, rgb.g, rgb.b),
+        hex: hex(rgb),
+        size,
+        mean,
+        variance,
+    })
+}
+
+/**
+ * 取色流程, 不依赖系统钩子, 方便替换输入来源
+ * next_event: 等待下一个输入事件, cursor: 当前鼠标位置, held: 按键是否按住
+ * 每隔 interval 推送一次鼠标位置和颜色, 按下快捷键时取色, 按 Esc 或超时结束
+ */
+pub fn run_picker(
+    options: &PickOptions,
+    source: &dyn ScreenSource,
+    mut next_event: impl FnMut(Duration) -> Result<InputEvent, RecvTimeoutError>,
+    cursor: impl Fn() -> Option<(i32, i32)>,
+    held: impl Fn(u32) -> bool,
+    mut on_update: impl FnMut(&PickUpdate),
+) -> Result<PickOutcome, String> {
+    // 没有设置快捷键时和原来一样, 按 F1 或左 Ctrl 都可以取色
+    let hotkeys = match options.hotkey.as_deref() {
+        Some(hotkey) => vec![HotKey::parse(hotkey)?],
+        None => vec![
+            HotKey::parse("F1")?,
+            HotKey {
+                modifiers: Vec::new(),
+                code: CTRL,
             },
-            ..Default::default()
-        };
-        let mut pixel = [0u8; 3];
-        GetDIBits(
-            hdc_mem,
-            hbm,
-            0,
-            1,
-            Some(pixel.as_mut_ptr() as *mut _),
-            &mut bmi,
-            DIB_RGB_COLORS,
-        );
-
-        let _ = DeleteObject(hbm.into());
-        let _ = DeleteDC(hdc_mem);
-
-        // 注意：像素顺序是 BGR
-        RGB::new(pixel[2], pixel[1], pixel[0])
+        ],
+    };
+    let timeout = Duration::from_millis(options.timeout.unwrap_or(30000));
+    let interval = Duration::from_millis(options.interval.unwrap_or(50).max(1));
+    let size = options.size.unwrap_or(5);
+    let deadline = Instant::now() + timeout;
+    let mut last_update: Option<Instant> = None;
+
+    loop {
+        if CANCEL.swap(false, Ordering::Relaxed) {
+            return Ok(PickOutcome::Cancelled);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(PickOutcome::TimedOut);
+        }
+        if last_update.is_none_or(|t| now.duration_since(t) >= interval) {
+            last_update = Some(now);
+            if let Some((x, y)) = cursor() {
+                if let Ok(rgb) = source.pixel(x, y) {
+                    on_update(&PickUpdate {
+                        x,
+                        y,
+                        rgb: format!("{},{},{}", rgb.r, rgb.g, rgb.b),
+                        hex: hex(rgb),
+                    });
+                }
+            }
+        }
+        match next_event(interval.min(deadline - now)) {
+            Ok(event) if event.pressed && event.code == ESC => return Ok(PickOutcome::Cancelled),
+            Ok(event) if event.pressed && hotkeys.iter().any(|h| h.matches(event.code, &held)) => {
+                let (x, y) = cursor().ok_or_else(|| "读取不到鼠标位置".to_string())?;
+                return pick_at(source, x, y, size).map(PickOutcome::Picked);
+            }
+            Ok(_) | Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return Err("输入监听已断开".to_string()),
+        }
+    }
+}
+
+// 取色结束时清除取色标识
+struct PickingGuard;
+
+impl Drop for PickingGuard {
+    fn drop(&mut self) {
+        PICKING.store(false, Ordering::Relaxed);
     }
 }
 
-pub fn mouse_rgb() -> String {
+// 当前鼠标位置, Windows 直接读取, 其他系统使用输入钩子记录的位置
+fn cursor() -> Option<(i32, i32)> {
+    #[cfg(windows)]
     unsafe {
-        let mut cursor_pos = POINT { x: 0, y: 0 };
-        loop {
-            let _ = GetCursorPos(&mut cursor_pos);
-            let device_state = DeviceState::new();
-            let keys: Vec<Keycode> = device_state.get_keys();
-
-            match keys.first() {
-                Some(Keycode::F1) | Some(Keycode::LControl) => break,
-                _ => continue,
+        use windows::Win32::{Foundation::POINT, UI::WindowsAndMessaging::GetCursorPos};
+        let mut pos = POINT { x: 0, y: 0 };
+        if GetCursorPos(&mut pos).is_ok() {
+            return Some((pos.x, pos.y));
+        }
+    }
+    cursor_position()
+}
+
+// 开始取色过程, 已经在取色时返回错误
+fn begin_pick() -> Result<PickingGuard, String> {
+    if PICKING.swap(true, Ordering::Relaxed) {
+        return Err("正在取色中".to_string());
+    }
+    CANCEL.store(false, Ordering::Relaxed);
+    Ok(PickingGuard)
+}
+
+// 在已开始的取色过程中等待快捷键取色
+fn pick_with(
+    options: &PickOptions,
+    subscription: &Subscription,
+    on_update: impl FnMut(&PickUpdate),
+) -> Result<PickOutcome, String> {
+    let source = screen()?;
+    run_picker(
+        options,
+        source.as_ref(),
+        |timeout| subscription.recv_timeout(timeout),
+        cursor,
+        is_held,
+        on_update,
+    )
+}
+
+// 等待 wait, 期间按下 Esc 或调用 cancel_pick 时返回 false
+fn wait_or_cancel(subscription: &Subscription, wait: Duration) -> bool {
+    let deadline = Instant::now() + wait;
+    loop {
+        if CANCEL.swap(false, Ordering::Relaxed) {
+            return false;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return true;
+        }
+        match subscription.recv_timeout(deadline - now) {
+            Ok(event) if event.pressed && event.code == ESC => return false,
+            Ok(_) | Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => {
+                thread::sleep(deadline - now);
+                return true;
             }
         }
+    }
+}
+
+/**
+ * 开始取色, 同一时间只能有一个取色过程
+ * 取色期间通过 on_update 推送鼠标位置和颜色
+ */
+pub fn pick(
+    options: &PickOptions,
+    on_update: impl FnMut(&PickUpdate),
+) -> Result<PickOutcome, String> {
+    let _guard = begin_pick()?;
+    let subscription = subscribe();
+    pick_with(options, &subscription, on_update)
+}
+
+/// 取消正在进行的取色
+pub fn cancel_pick() {
+    if PICKING.load(Ordering::Relaxed) {
+        CANCEL.store(true, Ordering::Relaxed);
+    }
+}
 
-        let rgb =  pick_up_rgb(cursor_pos.x, cursor_pos.y);
-        format!(
-            "{},{}|{},{},{}",
-            cursor_pos.x, cursor_pos.y, rgb.r, rgb.g, rgb.b
-        )
+/// 取色结果, 取消或超时时返回错误
+pub fn picked(outcome: PickOutcome) -> Result<PickResult, String> {
+    match outcome {
+        PickOutcome::Picked(result) => Ok(result),
+        PickOutcome::Cancelled => Err("已取消取色".to_string()),
+        PickOutcome::TimedOut => Err("取色超时".to_string()),
     }
-}
\ No newline at end of file
+}
+
+/// 鼠标位置的坐标和颜色, 格式 "x,y|r,g,b"
+pub fn mouse_rgb() -> Result<String, String> {
+    let result = picked(pick(&PickOptions::default(), |_| {})?)?;
+    Ok(format!("{}|{}", result.coordinate, result.rgb))
+}
+
+/**
+ * 采样取色, 在鼠标位置每隔 interval 毫秒取色一次, 共 samples 次
+ * 根据采样到的波动建议偏差, 用于闪烁或有特效覆盖的位置
+ * 取色和采样属于同一个取色过程, 期间都可以按 Esc 或调用 cancel_pick 取消
+ */
+pub fn mouse_rgb_samples(
+    metric: ColorMetric,
+    samples: u32,
+    interval: u64,
+    mut on_update: impl FnMut(&PickUpdate),
+) -> Result<ColorSample, String> {
+    let _guard = begin_pick()?;
+    let subscription = subscribe();
+    let result = picked(pick_with(
+        &PickOptions::default(),
+        &subscription,
+        &mut on_update,
+    )?)?;
+    let (x, y) = parse_coordinate(&result.coordinate)
+        .ok_or_else(|| format!("坐标有误: {}", result.coordinate))?;
+    let mut colors = Vec::new();
+    for i in 0..samples.max(1) {
+        if i > 0 && !wait_or_cancel(&subscription, Duration::from_millis(interval)) {
+            return Err("已取消取色".to_string());
+        }
+        let rgb = get_coordinate_rgb(x, y);
+        on_update(&PickUpdate {
+            x,
+            y,
+            rgb: format!("{},{},{}", rgb.r, rgb.g, rgb.b),
+            hex: hex(rgb),
+        });
+        colors.push(rgb);
+    }
+    // colors 不为空, 一定有建议值
+    let suggestion = suggest_tolerance(metric, &colors).unwrap();
+    let rgb = suggestion.rgb;
+    Ok(ColorSample {
+        coordinate: result.coordinate,
+        rgb: format!("{},{},{}", rgb.r, rgb.g, rgb.b),
+        tolerance: suggestion.tolerance,
+        metric,
+        saturation: suggestion.saturation,
+    })
+}
@@ -0,0 +1,155 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+use serde::Serialize;
+
+use crate::{
+    global::model::Color,
+    rgb::{
+        capture::FrameCache,
+        check::{parse_coordinate, parse_rgb},
+        metric::{color_distance, color_matches},
+        pick::hex,
+        source::ScreenSource,
+    },
+};
+
+const MIN_RATE: u64 = 16; // 最短推送间隔ms
+
+lazy_static! {
+    static ref WATCHES: Mutex<HashMap<u64, Arc<AtomicBool>>> = Mutex::new(HashMap::new()); // 订阅的停止标识
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 一个坐标当前的颜色, 取色节点没有填写颜色时不判断是否匹配
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PixelReading {
+    pub coordinate: String,
+    pub rgb: Option<String>,   // 当前颜色 "r,g,b"
+    pub hex: Option<String>,   // 当前颜色 "#rrggbb"
+    pub matched: Option<bool>, // 是否和取色节点的颜色匹配
+    pub distance: Option<f64>, // 按取色节点的 metric 计算的差异
+    pub error: Option<String>, // 坐标有误或读取失败
+}
+
+/// 推送给界面的一组读数
+#[derive(Debug, Clone, Serialize)]
+pub struct PixelFrame {
+    pub id: u64, // 订阅id
+    pub pixels: Vec<PixelReading>,
+}
+
+// 读取失败时的读数
+fn failed(coordinate: &str, error: String) -> PixelReading {
+    PixelReading {
+        coordinate: coordinate.to_string(),
+        rgb: None,
+        hex: None,
+        matched: None,
+        distance: None,
+        error: Some(error),
+    }
+}
+
+/**
+ * 读取所有取色节点坐标的颜色, 和执行规则时一样只截图一次
+ */
+pub fn read_pixels(
+    source: &dyn ScreenSource,
+    probes: &[Color],
+    frames: &mut FrameCache,
+) -> Vec<PixelReading> {
+    frames.begin_cycle();
+    let now = Instant::now();
+    probes
+        .iter()
+        .map(|co| {
+            let (x, y) = match parse_coordinate(&co.coordinate) {
+                Some(point) => point,
+                None => return failed(&co.coordinate, format!("坐标有误: {}", co.coordinate)),
+            };
+            let rgb = match frames.pixel(source, x, y, now) {
+                Ok(rgb) => rgb,
+                Err(e) => return failed(&co.coordinate, e),
+            };
+            let target = parse_rgb(&co.rgb);
+            PixelReading {
+                coordinate: co.coordinate.clone(),
+                rgb: Some(format!("{},{},{}", rgb.r, rgb.g, rgb.b)),
+                hex: Some(hex(rgb)),
+                matched: target
+                    .map(|t| color_matches(co.metric, rgb, t, co.tolerance, co.saturation)),
+                distance: target.map(|t| color_distance(co.metric, rgb, t)),
+                error: None,
+            }
+        })
+        .collect()
+}
+
+/// 订阅使用的截图缓存, 截取所有坐标所在的区域
+pub fn probe_frames(probes: &[Color]) -> FrameCache {
+    let points: Vec<(i32, i32)> = probes
+        .iter()
+        .filter_map(|co| parse_coordinate(&co.coordinate))
+        .collect();
+    FrameCache::new(&points, None)
+}
+
+/**
+ * 订阅一组坐标, 每隔 rate 毫秒读取一次并交给 on_frame
+ * on_frame 返回 false (例如窗口已关闭) 或取消订阅时停止, 返回订阅id
+ */
+pub fn watch_pixels(
+    source: Arc<dyn ScreenSource>,
+    probes: Vec<Color>,
+    rate: u64,
+    mut on_frame: impl FnMut(PixelFrame) -> bool + Send + 'static,
+) -> u64 {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let stop = Arc::new(AtomicBool::new(false));
+    WATCHES.lock().unwrap().insert(id, stop.clone());
+    let rate = Duration::from_millis(rate.max(MIN_RATE));
+
+    thread::spawn(move || {
+        let mut frames = probe_frames(&probes);
+        while !stop.load(Ordering::Relaxed) {
+            let started = Instant::now();
+            let pixels = read_pixels(source.as_ref(), &probes, &mut frames);
+            if !on_frame(PixelFrame { id, pixels }) {
+                break;
+            }
+            if let Some(wait) = rate.checked_sub(started.elapsed()) {
+                thread::sleep(wait);
+            }
+        }
+        WATCHES.lock().unwrap().remove(&id);
+    });
+    id
+}
+
+/// 取消订阅, 订阅不存在时返回 false
+pub fn unwatch_pixels(id: u64) -> bool {
+    match WATCHES.lock().unwrap().remove(&id) {
+        Some(stop) => {
+            stop.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+/// 取消所有订阅, 窗口关闭时调用
+pub fn unwatch_all() {
+    for (_, stop) in WATCHES.lock().unwrap().drain() {
+        stop.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,120 @@
+use std::sync::{Arc, Mutex};
+
+use lazy_static::lazy_static;
+
+use crate::{global::common::RGB, rgb::monitor::Monitor};
+
+/// 屏幕区域的像素, 按行存储, 坐标为屏幕坐标
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<RGB>,
+}
+
+impl Frame {
+    pub fn new(x: i32, y: i32, width: u32, height: u32, pixels: Vec<RGB>) -> Self {
+        Frame {
+            x,
+            y,
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// 屏幕坐标 (x, y) 的颜色, 不在区域内时返回 None
+    pub fn pixel(&self, x: i32, y: i32) -> Option<RGB> {
+        let dx = x.checked_sub(self.x)?;
+        let dy = y.checked_sub(self.y)?;
+        if dx < 0 || dy < 0 || dx as u32 >= self.width || dy as u32 >= self.height {
+            return None;
+        }
+        self.pixels
+            .get(dy as usize * self.width as usize + dx as usize)
+            .copied()
+    }
+}
+
+/**
+ * 屏幕画面来源, 取色和区域截图都通过它读取
+ * Windows 使用 GDI, Linux 使用 X11, 也可以使用保存的截图文件
+ */
+pub trait ScreenSource: Send + Sync {
+    /// 读取左上角为 (x, y) 的区域
+    fn region(&self, x: i32, y: i32, width: u32, height: u32) -> Result<Frame, String>;
+
+    /// 主显示器尺寸
+    fn size(&self) -> Result<(u32, u32), String>;
+
+    /// 所有显示器, 默认只有一个从 (0, 0) 开始的主显示器
+    fn monitors(&self) -> Result<Vec<Monitor>, String> {
+        let (width, height) = self.size()?;
+        Ok(vec![Monitor {
+            index: 1,
+            name: "primary".to_string(),
+            x: 0,
+            y: 0,
+            width,
+            height,
+            primary: true,
+        }])
+    }
+
+    /// 读取单个像素
+    fn pixel(&self, x: i32, y: i32) -> Result<RGB, String> {
+        self.region(x, y, 1, 1)?
+            .pixel(x, y)
+            .ok_or_else(|| format!("读取像素失败: {},{}", x, y))
+    }
+}
+
+lazy_static! {
+    static ref SOURCE: Mutex<Option<Arc<dyn ScreenSource>>> = Mutex::new(None); // 当前使用的画面来源
+}
+
+/**
+ * 当前使用的画面来源, 第一次调用时创建
+ * 设置了环境变量 RUBY_SCREEN_FILE 时从该截图文件读取
+ * 虚拟桌面的截图可以用 RUBY_SCREEN_ORIGIN="x,y" 指定左上角的坐标
+ */
+pub fn screen() -> Result<Arc<dyn ScreenSource>, String> {
+    let mut source = SOURCE.lock().unwrap();
+    if let Some(source) = source.as_ref() {
+        return Ok(source.clone());
+    }
+    let created = default_source()?;
+    *source = Some(created.clone());
+    Ok(created)
+}
+
+/// 替换画面来源, 例如改为从截图文件读取
+pub fn set_screen(source: Arc<dyn ScreenSource>) {
+    *SOURCE.lock().unwrap() = Some(source);
+}
+
+fn default_source() -> Result<Arc<dyn ScreenSource>, String> {
+    if let Ok(path) = std::env::var("RUBY_SCREEN_FILE") {
+        let mut source = crate::rgb::file::ImageSource::open(path)?;
+        if let Ok(origin) = std::env::var("RUBY_SCREEN_ORIGIN") {
+            let (x, y) = crate::rgb::check::parse_coordinate(&origin)
+                .ok_or_else(|| format!("截图左上角坐标有误: {}", origin))?;
+            source = source.with_origin(x, y);
+        }
+        return Ok(Arc::new(source));
+    }
+    #[cfg(windows)]
+    {
+        Ok(Arc::new(crate::rgb::gdi::GdiSource))
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Ok(Arc::new(crate::rgb::x11::X11Source::connect()?))
+    }
+    #[cfg(not(any(windows, target_os = "linux")))]
+    {
+        Err("当前系统不支持读取屏幕".to_string())
+    }
+}
@@ -0,0 +1,150 @@
+use std::path::{Path, PathBuf};
+
+use image::{Rgb, RgbImage, RgbaImage};
+
+use crate::{global::common::RGB, rgb::source::Frame, user::user::get_templates_dir};
+
+/// 模板匹配结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TemplateMatch {
+    pub x: i32,          // 匹配位置左上角
+    pub y: i32,          //
+    pub similarity: f64, // 相似度 0-1
+}
+
+/**
+ * 模板图片, 例如 buff 图标
+ * png 中透明度低于一半的像素不参与比较
+ */
+pub struct TemplateImage {
+    pub width: u32,
+    pub height: u32,
+    pixels: Vec<(u32, u32, RGB)>, // 参与比较的像素
+}
+
+/// 模板名称对应的文件, 名称不能包含路径
+pub fn template_path(name: &str) -> Result<PathBuf, String> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+        return Err(format!("模板名称有误: {}", name));
+    }
+    let dir = get_templates_dir().ok_or_else(|| "找不到配置目录".to_string())?;
+    let file = if name.ends_with(".png") {
+        name.to_string()
+    } else {
+        format!("{}.png", name)
+    };
+    Ok(dir.join(file))
+}
+
+impl TemplateImage {
+    pub fn open(path: impl AsRef<Path>) -> Result<TemplateImage, String> {
+        let path = path.as_ref();
+        let image = image::open(path)
+            .map_err(|e| format!("读取模板失败 {}: {}", path.display(), e))?
+            .to_rgba8();
+        TemplateImage::from_image(&image)
+            .ok_or_else(|| format!("模板没有可比较的像素: {}", path.display()))
+    }
+
+    /// 使用内存中的图片, 没有可比较的像素时返回 None
+    pub fn from_image(image: &RgbaImage) -> Option<TemplateImage> {
+        let mut pixels = Vec::new();
+        for (x, y, p) in image.enumerate_pixels() {
+            let [r, g, b, a] = p.0;
+            if a >= 128 {
+                pixels.push((x, y, RGB::new(r, g, b)));
+            }
+        }
+        if pixels.is_empty() {
+            return None;
+        }
+        Some(TemplateImage {
+            width: image.width(),
+            height: image.height(),
+            pixels,
+        })
+    }
+
+    /// 读取配置目录中的模板
+    pub fn load(name: &str) -> Result<TemplateImage, String> {
+        TemplateImage::open(template_path(name)?)
+    }
+
+    /**
+     * 在截图的 region 区域内查找模板, 模板需要完整落在区域内
+     * 返回相似度不低于 threshold 的最佳位置
+     */
+    pub fn find(
+        &self,
+        frame: &Frame,
+        region: (i32, i32, u32, u32),
+        threshold: f64,
+    ) -> Option<TemplateMatch> {
+        let (rx, ry, width, height) = region;
+        if width < self.width || height < self.height {
+            return None;
+        }
+        // 相似度 = 1 - 平均每个通道的差值 / 255, 换算成允许的差值总和
+        let total = (self.pixels.len() * 3 * 255) as f64;
+        let mut budget = ((1.0 - threshold.clamp(0.0, 1.0)) * total) as u64;
+        let mut best: Option<(i32, i32, u64)> = None;
+
+        for y in ry..=ry + (height - self.height) as i32 {
+            for x in rx..=rx + (width - self.width) as i32 {
+                if let Some(diff) = self.difference(frame, x, y, budget) {
+                    best = Some((x, y, diff));
+                    if diff == 0 {
+                        break;
+                    }
+                    // 之后只接受更好的位置
+                    budget = diff.saturating_sub(1);
+                }
+            }
+            if matches!(best, Some((_, _, 0))) {
+                break;
+            }
+        }
+        best.map(|(x, y, diff)| TemplateMatch {
+            x,
+            y,
+            similarity: 1.0 - diff as f64 / total,
+        })
+    }
+
+    // 模板左上角在 (x, y) 时的差值总和, 超过 budget 或超出截图时返回 None
+    fn difference(&self, frame: &Frame, x: i32, y: i32, budget: u64) -> Option<u64> {
+        let mut diff = 0u64;
+        for (px, py, rgb) in &self.pixels {
+            let color = frame.pixel(x + *px as i32, y + *py as i32)?;
+            diff += (color.r as i32 - rgb.r as i32).unsigned_abs() as u64
+                + (color.g as i32 - rgb.g as i32).unsigned_abs() as u64
+                + (color.b as i32 - rgb.b as i32).unsigned_abs() as u64;
+            if diff > budget {
+                return None;
+            }
+        }
+        Some(diff)
+    }
+}
+
+/// 截图转换为图片, 用于保存模板
+pub fn frame_to_image(frame: &Frame) -> RgbImage {
+    RgbImage::from_fn(frame.width, frame.height, |x, y| {
+        let p = frame.pixels[(y * frame.width + x) as usize];
+        Rgb([p.r, p.g, p.b])
+    })
+}
+
+/**
+ * 截取屏幕区域保存为模板
+ */
+pub fn save_template(name: &str, frame: &Frame) -> Result<PathBuf, String> {
+    let path = template_path(name)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("创建模板目录失败: {}", e))?;
+    }
+    frame_to_image(frame)
+        .save(&path)
+        .map_err(|e| format!("保存模板失败: {}", e))?;
+    Ok(path)
+}
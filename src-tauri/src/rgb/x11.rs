@@ -0,0 +1,182 @@
+use x11rb::{
+    connection::Connection,
+    protocol::{
+        randr::ConnectionExt as _,
+        xproto::{ConnectionExt, ImageFormat, ImageOrder, Window},
+    },
+    rust_connection::RustConnection,
+};
+
+use crate::{
+    global::common::RGB,
+    rgb::{
+        monitor::{relative_to_primary, sort_monitors, Monitor},
+        source::{Frame, ScreenSource},
+    },
+};
+
+/**
+ * 通过 X11 读取根窗口画面, 可以在 Xvfb 下运行
+ * RandR 的显示器位置以根窗口左上角为原点, 和 Windows 一样换算为以主显示器左上角为原点
+ */
+pub struct X11Source {
+    conn: RustConnection,
+    root: Window,
+    origin: (i32, i32),  // 主显示器在根窗口中的位置
+    width: u32,          // 主显示器大小, 没有 RandR 时为根窗口大小
+    height: u32,         //
+    bits_per_pixel: u32, // 每个像素占用的位数
+    scanline_pad: u32,   // 每行按多少位对齐
+    lsb_first: bool,     // 像素字节顺序
+    masks: [u32; 3],     // 红绿蓝通道的掩码
+}
+
+impl X11Source {
+    /// 连接 DISPLAY 环境变量指定的显示
+    pub fn connect() -> Result<X11Source, String> {
+        let (conn, screen_num) = x11rb::connect(None).map_err(|e| format!("连接X11失败: {}", e))?;
+        let setup = conn.setup();
+        let screen = &setup.roots[screen_num];
+        let format = setup
+            .pixmap_formats
+            .iter()
+            .find(|f| f.depth == screen.root_depth)
+            .ok_or_else(|| format!("不支持的颜色深度: {}", screen.root_depth))?;
+        let visual = screen
+            .allowed_depths
+            .iter()
+            .flat_map(|d| d.visuals.iter())
+            .find(|v| v.visual_id == screen.root_visual)
+            .ok_or_else(|| "找不到根窗口的视觉类型".to_string())?;
+        if format.bits_per_pixel < 8 {
+            return Err(format!("不支持的像素格式: {}位", format.bits_per_pixel));
+        }
+        let mut source = X11Source {
+            root: screen.root,
+            origin: (0, 0),
+            width: screen.width_in_pixels as u32,
+            height: screen.height_in_pixels as u32,
+            bits_per_pixel: format.bits_per_pixel as u32,
+            scanline_pad: format.scanline_pad as u32,
+            lsb_first: setup.image_byte_order == ImageOrder::LSB_FIRST,
+            masks: [visual.red_mask, visual.green_mask, visual.blue_mask],
+            conn,
+        };
+        let mut monitors = source.root_monitors();
+        source.origin = relative_to_primary(&mut monitors);
+        if let Some(primary) = monitors.iter().find(|m| m.primary) {
+            (source.width, source.height) = (primary.width, primary.height);
+        }
+        Ok(source)
+    }
+
+    // 按掩码取出通道值并缩放到 0-255
+    fn channel(value: u32, mask: u32) -> u8 {
+        if mask == 0 {
+            return 0;
+        }
+        let max = mask >> mask.trailing_zeros();
+        (((value & mask) >> mask.trailing_zeros()) * 255 / max) as u8
+    }
+
+    // 通过 RandR 读取显示器, 位置以根窗口左上角为原点, 不支持时整个根窗口作为一个显示器
+    fn root_monitors(&self) -> Vec<Monitor> {
+        let reply = match self
+            .conn
+            .randr_get_monitors(self.root, true)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+        {
+            Some(reply) if !reply.monitors.is_empty() => reply,
+            _ => {
+                return vec![Monitor {
+                    index: 1,
+                    name: "root".to_string(),
+                    x: 0,
+                    y: 0,
+                    width: self.width,
+                    height: self.height,
+                    primary: true,
+                }]
+            }
+        };
+        let mut monitors = Vec::new();
+        for m in reply.monitors {
+            let name = self
+                .conn
+                .get_atom_name(m.name)
+                .ok()
+                .and_then(|cookie| cookie.reply().ok())
+                .map(|r| String::from_utf8_lossy(&r.name).into_owned())
+                .unwrap_or_default();
+            monitors.push(Monitor {
+                index: 0,
+                name,
+                x: m.x as i32,
+                y: m.y as i32,
+                width: m.width as u32,
+                height: m.height as u32,
+                primary: m.primary,
+            });
+        }
+        monitors
+    }
+}
+
+impl ScreenSource for X11Source {
+    fn region(&self, x: i32, y: i32, width: u32, height: u32) -> Result<Frame, String> {
+        let reply = self
+            .conn
+            .get_image(
+                ImageFormat::Z_PIXMAP,
+                self.root,
+                (x + self.origin.0) as i16,
+                (y + self.origin.1) as i16,
+                width as u16,
+                height as u16,
+                !0,
+            )
+            .map_err(|e| format!("截图失败: {}", e))?
+            .reply()
+            .map_err(|e| format!("截图失败: {}", e))?;
+
+        let bytes = (self.bits_per_pixel / 8) as usize;
+        let pad = self.scanline_pad.max(8);
+        let stride = ((width * self.bits_per_pixel).div_ceil(pad) * pad / 8) as usize;
+        let mut pixels = Vec::with_capacity(width as usize * height as usize);
+        for row in 0..height as usize {
+            for col in 0..width as usize {
+                let offset = row * stride + col * bytes;
+                let data = reply
+                    .data
+                    .get(offset..offset + bytes)
+                    .ok_or_else(|| "截图数据不完整".to_string())?;
+                let value = if self.lsb_first {
+                    data.iter().rev().fold(0u32, |v, b| (v << 8) | *b as u32)
+                } else {
+                    data.iter().fold(0u32, |v, b| (v << 8) | *b as u32)
+                };
+                pixels.push(RGB::new(
+                    Self::channel(value, self.masks[0]),
+                    Self::channel(value, self.masks[1]),
+                    Self::channel(value, self.masks[2]),
+                ));
+            }
+        }
+        Ok(Frame::new(x, y, width, height, pixels))
+    }
+
+    fn size(&self) -> Result<(u32, u32), String> {
+        Ok((self.width, self.height))
+    }
+
+    /// 显示器位置以主显示器左上角为原点, 和截图坐标一致
+    fn monitors(&self) -> Result<Vec<Monitor>, String> {
+        let mut monitors = self.root_monitors();
+        for m in monitors.iter_mut() {
+            m.x -= self.origin.0;
+            m.y -= self.origin.1;
+        }
+        Ok(sort_monitors(monitors))
+    }
+}
@@ -0,0 +1,119 @@
+use serde::Serialize;
+
+use crate::{
+    act::trigger::{mode_name, resolve_triggers},
+    global::{global::ACTION_TYPE, model::Elements},
+    input::code::code_name,
+    start::hotkey::{load_hotkeys, HotKey, HotKeys},
+};
+
+/// 规则或全局快捷键的一个触发键
+#[derive(Debug, Clone)]
+pub struct TriggerKey {
+    pub owner: String,       // 所属规则或快捷键的名称
+    pub rule: Option<usize>, // 规则编号, 全局快捷键为 None
+    pub chord: HotKey,       // 触发键及需要同时按住的修饰键
+    pub mode: String,        // 触发模式
+}
+
+/// 触发键冲突
+#[derive(Debug, Clone, Serialize)]
+pub struct Conflict {
+    pub first: String,  // 冲突的一方
+    pub second: String, // 冲突的另一方
+    pub key: String,    // 冲突的按键
+    pub reason: String, // 冲突原因
+}
+
+/// 规则的触发键, 定时和条件触发的规则没有触发键
+pub fn rule_triggers(elements: &[Elements], action_type: u32) -> Vec<TriggerKey> {
+    let mut triggers = Vec::new();
+    for (id, e) in elements.iter().enumerate() {
+        if e.timer.is_some() || e.watch.is_some() {
+            continue;
+        }
+        for (trigger, mode) in resolve_triggers(e, action_type) {
+            let name = code_name(trigger.code).unwrap_or(&e.header.elements_key);
+            triggers.push(TriggerKey {
+                owner: format!("规则{}({})", id + 1, name),
+                rule: Some(id),
+                chord: HotKey {
+                    modifiers: Vec::new(),
+                    code: trigger.code,
+                },
+                mode: mode_name(mode).to_string(),
+            });
+        }
+    }
+    triggers
+}
+
+pub fn hotkey_triggers(hotkeys: &HotKeys) -> Vec<TriggerKey> {
+    hotkeys
+        .keys
+        .iter()
+        .map(|(action, key)| TriggerKey {
+            owner: format!("快捷键{:?}", action),
+            rule: None,
+            chord: key.clone(),
+            mode: "快捷键".to_string(),
+        })
+        .collect()
+}
+
+// 按下 a 的全部按键时 b 是否也会触发
+fn fires(a: &HotKey, b: &HotKey) -> bool {
+    let pressed = |code: &u32| *code == a.code || a.modifiers.contains(code);
+    pressed(&b.code) && b.modifiers.iter().all(pressed)
+}
+
+/**
+ * 检查触发键之间的冲突
+ * 包括触发键相同(无论模式是否相同)和组合键中包含其他规则的触发键
+ */
+pub fn detect_conflicts(triggers: &[TriggerKey]) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    for (i, a) in triggers.iter().enumerate() {
+        for b in triggers.iter().skip(i + 1) {
+            // 同一条规则的多个触发键之间、全局快捷键之间不算冲突
+            if a.rule == b.rule {
+                continue;
+            }
+            let (key, reason) = if a.chord == b.chord {
+                let reason = if a.mode == b.mode {
+                    "触发键相同".to_string()
+                } else {
+                    format!("触发键相同但模式不同({} / {})", a.mode, b.mode)
+                };
+                (&a.chord, reason)
+            } else if fires(&a.chord, &b.chord) {
+                (
+                    &b.chord,
+                    format!("按下 {} 时 {} 也会触发", a.chord, b.chord),
+                )
+            } else if fires(&b.chord, &a.chord) {
+                (
+                    &a.chord,
+                    format!("按下 {} 时 {} 也会触发", b.chord, a.chord),
+                )
+            } else {
+                continue;
+            };
+            conflicts.push(Conflict {
+                first: a.owner.clone(),
+                second: b.owner.clone(),
+                key: key.to_string(),
+                reason,
+            });
+        }
+    }
+    conflicts
+}
+
+/// 检查规则之间以及规则与全局快捷键之间的冲突
+pub fn find_conflicts(elements: &[Elements]) -> Vec<Conflict> {
+    let action_type = *ACTION_TYPE.lock().unwrap();
+    let mut triggers = rule_triggers(elements, action_type);
+    triggers.extend(hotkey_triggers(&load_hotkeys()));
+    detect_conflicts(&triggers)
+}
@@ -0,0 +1,186 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::RecvTimeoutError,
+    },
+    thread,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    global::global::{MODE_CLOSE, STOP_ACTION, TIME_WITE},
+    input::{
+        code::{code_name, is_modifier_code, name_to_code, MODIFIER_CODES},
+        hook::{is_held, subscribe},
+    },
+    start::start::close,
+    user::user::get_hotkeys,
+};
+
+/// 组合键, 例如 Ctrl+Shift+F2
+#[derive(Debug, Clone, PartialEq)]
+pub struct HotKey {
+    pub modifiers: Vec<u32>, // 修饰键
+    pub code: u32,           // 主键
+}
+
+impl HotKey {
+    pub fn parse(s: &str) -> Result<HotKey, String> {
+        let mut modifiers = Vec::new();
+        let mut code = None;
+        for part in s.split('+') {
+            let c = name_to_code(part).ok_or_else(|| format!("无法识别的按键: {}", part.trim()))?;
+            if is_modifier_code(c) && !modifiers.contains(&c) {
+                modifiers.push(c);
+            } else if code.is_none() {
+                code = Some(c);
+            } else {
+                return Err(format!("组合键只能包含一个主键: {}", s));
+            }
+        }
+        // 单独的修饰键也可以作为主键
+        let code = match code {
+            Some(c) => c,
+            None => modifiers
+                .pop()
+                .ok_or_else(|| "快捷键不能为空".to_string())?,
+        };
+        modifiers.sort_by_key(|m| MODIFIER_CODES.iter().position(|c| c == m));
+        Ok(HotKey { modifiers, code })
+    }
+
+    /// 主键按下时修饰键是否恰好匹配, 其他非修饰键是否按下不影响
+    pub fn matches(&self, code: u32, held: impl Fn(u32) -> bool) -> bool {
+        code == self.code
+            && MODIFIER_CODES
+                .iter()
+                .filter(|m| **m != self.code)
+                .all(|m| held(*m) == self.modifiers.contains(m))
+    }
+}
+
+impl fmt::Display for HotKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for m in &self.modifiers {
+            write!(f, "{}+", code_name(*m).unwrap_or("?"))?;
+        }
+        write!(f, "{}", code_name(self.code).unwrap_or("?"))
+    }
+}
+
+/// 用户配置中的全局快捷键, 为空表示不启用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotKeyConfig {
+    pub pause: Option<String>,          // 暂停
+    pub resume: Option<String>,         // 恢复
+    pub toggle: Option<String>,         // 暂停/恢复切换
+    pub emergency_stop: Option<String>, // 紧急停止, 关闭所有规则
+}
+
+impl Default for HotKeyConfig {
+    fn default() -> Self {
+        HotKeyConfig {
+            pause: Some("F2".to_string()),
+            resume: Some("F3".to_string()),
+            toggle: None,
+            emergency_stop: Some("Ctrl+F12".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HotKeyAction {
+    Pause,
+    Resume,
+    Toggle,
+    EmergencyStop,
+}
+
+/// 解析后的全局快捷键
+#[derive(Debug, Clone, Default)]
+pub struct HotKeys {
+    pub keys: Vec<(HotKeyAction, HotKey)>,
+}
+
+impl HotKeys {
+    pub fn from_config(config: &HotKeyConfig) -> Result<HotKeys, String> {
+        let mut keys = Vec::new();
+        for (action, value) in [
+            (HotKeyAction::Pause, &config.pause),
+            (HotKeyAction::Resume, &config.resume),
+            (HotKeyAction::Toggle, &config.toggle),
+            (HotKeyAction::EmergencyStop, &config.emergency_stop),
+        ] {
+            if let Some(s) = value.as_deref().filter(|s| !s.trim().is_empty()) {
+                let key = HotKey::parse(s)?;
+                if let Some((other, _)) = keys.iter().find(|(_, k)| *k == key) {
+                    return Err(format!(
+                        "快捷键 {} 同时用于 {:?} 和 {:?}",
+                        key, other, action
+                    ));
+                }
+                keys.push((action, key));
+            }
+        }
+        Ok(HotKeys { keys })
+    }
+
+    pub fn action(&self, code: u32, held: impl Fn(u32) -> bool) -> Option<HotKeyAction> {
+        self.keys
+            .iter()
+            .find(|(_, key)| key.matches(code, &held))
+            .map(|(action, _)| *action)
+    }
+}
+
+// 每次开启规则都会重新启动快捷键监听, 旧的监听线程发现代数变化后退出
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 读取用户配置中的快捷键, 配置有误时使用默认值
+pub fn load_hotkeys() -> HotKeys {
+    let config = get_hotkeys().unwrap_or_default();
+    HotKeys::from_config(&config).unwrap_or_else(|e| {
+        eprintln!("快捷键配置有误, 使用默认配置: {}", e);
+        HotKeys::from_config(&HotKeyConfig::default()).unwrap_or_default()
+    })
+}
+
+/**
+ * 监听全局快捷键, 规则关闭后退出
+ */
+pub fn listen_hotkeys(hotkeys: HotKeys) {
+    let generation = GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    thread::spawn(move || {
+        let events = subscribe();
+        loop {
+            if GENERATION.load(Ordering::Relaxed) != generation || *MODE_CLOSE.lock().unwrap() {
+                break;
+            }
+            let event = match events.recv_timeout(Duration::from_millis(*TIME_WITE)) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+            if !event.pressed {
+                continue;
+            }
+            match hotkeys.action(event.code, is_held) {
+                Some(HotKeyAction::Pause) => *STOP_ACTION.lock().unwrap() = true,
+                Some(HotKeyAction::Resume) => *STOP_ACTION.lock().unwrap() = false,
+                Some(HotKeyAction::Toggle) => {
+                    let mut stop_action = STOP_ACTION.lock().unwrap();
+                    *stop_action = !*stop_action;
+                }
+                Some(HotKeyAction::EmergencyStop) => {
+                    close();
+                    break;
+                }
+                None => {}
+            }
+        }
+    });
+}
@@ -1 +1,4 @@
-pub mod start;
\ No newline at end of file
+pub mod conflict;
+pub mod hotkey;
+pub mod start;
+pub mod status;
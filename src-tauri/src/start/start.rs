@@ -1,13 +1,28 @@
-use std::thread;
-
-use device_query::{DeviceQuery, DeviceState, Keycode};
+use std::collections::HashSet;
 
 use crate::{
-    act::{keyboard::keyboard_type, mouse::mouse_type},
+    act::{
+        trigger::{resolve_triggers, trigger_type},
+        watch,
+    },
     global::{
-        global::{HOLD_ON_TIME, MODE_CLOSE, STOP_ACTION, TIME_CHECK_TIME, TIME_WITE},
+        common::{screen_height, screen_width},
+        global::{
+            ACTION_TYPE, HOLD_ON_TIME, MODE_CLOSE, SCREEN_HEIGHT, SCREEN_WIDTH, STOP_ACTION,
+            TIME_CHECK_TIME,
+        },
         init::{init_global_hdc_screen, release_global_hdc_screen},
-        model::Elements,
+        model::{CoordinateSpace, Elements, WatchCondition},
+    },
+    input::{code::code_name, hook::set_suppressed},
+    rgb::{
+        coord::{transform_elements, transform_for, window_client_rect},
+        source::screen,
+    },
+    start::{
+        conflict::{find_conflicts, Conflict},
+        hotkey::{listen_hotkeys, load_hotkeys},
+        status,
     },
     user::user::get_hod_on_time,
 };
@@ -15,9 +30,16 @@ use crate::{
 /**
  * element x6的元素集合
  * t: 开启或者关闭
+ * 触发键冲突时不开启任何规则, 返回冲突列表
  */
-pub fn start(element: Vec<Elements>, t: bool) {
+pub fn start(element: Vec<Elements>, t: bool) -> Result<(), Vec<Conflict>> {
     if t {
+        // 在安装任何监听之前检查触发键冲突
+        let conflicts = find_conflicts(&element);
+        if !conflicts.is_empty() {
+            return Err(conflicts);
+        }
+
         // get_hod_on_time 通过用户配置修改HOLD_ON_TIME
         let user_set_hold_on_time = get_hod_on_time();
         {
@@ -29,25 +51,100 @@ pub fn start(element: Vec<Elements>, t: bool) {
         }
 
         init_global_hdc_screen(); // 初始化全局屏幕句柄
+
+        // 分辨率可能在程序启动后改变, 坐标换算前重新读取
+        *SCREEN_WIDTH.lock().unwrap() = screen_width();
+        *SCREEN_HEIGHT.lock().unwrap() = screen_height();
     } else {
         release_global_hdc_screen(); // 释放全局屏幕句柄
         TIME_CHECK_TIME.lock().unwrap().clear(); // 清除时间检查缓存
-        return;
+        status::clear(); // 清除运行状态
+        watch::clear(); // 停止条件检测
+        return Ok(());
     }
     // 设置关闭标识为false 表示开启状态
     {
         let mut stop_action = STOP_ACTION.lock().unwrap();
         *stop_action = false;
     }
-    for e in element.iter() {
-        if e.header.elements_code == 4 || e.header.elements_code == 5 {
-            // 鼠标模式
-            mouse_type(e.clone());
-        } else {
-            // 这些都是键盘代码，使用键盘监听
-            keyboard_type(e.clone());
+    status::clear();
+    watch::clear();
+    let action_type = *ACTION_TYPE.lock().unwrap();
+    let mut suppressed = HashSet::new();
+    for (id, e) in element.iter().enumerate() {
+        let e = &match resolve_coordinates(e) {
+            Ok(e) => e,
+            Err(msg) => {
+                eprintln!("坐标换算失败: {}, 已跳过该规则", msg);
+                continue;
+            }
+        };
+        if let Some(timer) = &e.timer {
+            let name = match timer.interval {
+                Some(interval) => format!("每{}ms", interval),
+                None => format!("延迟{}ms", timer.delay.unwrap_or(0)),
+            };
+            status::register(id, name, "定时");
+            trigger_type(id, e.clone());
+            continue;
         }
+        if let Some(w) = &e.watch {
+            let name = match &w.condition {
+                WatchCondition::Pixel { coordinate, .. } => format!("像素 {}", coordinate),
+                WatchCondition::Mana { below, .. } => format!("内力 ≤ {}", below),
+            };
+            status::register(id, name, "条件");
+            trigger_type(id, e.clone());
+            continue;
+        }
+        // 键盘和鼠标触发都来自同一个输入钩子, 不支持的触发码会被忽略
+        let triggers = resolve_triggers(e, action_type);
+        if triggers.is_empty() {
+            eprintln!(
+                "没有可用的触发键: {} ({}), 已跳过该规则",
+                e.header.elements_code, e.header.elements_key
+            );
+            continue;
+        }
+        let name = triggers
+            .iter()
+            .map(|(t, _)| code_name(t.code).unwrap_or(&e.header.elements_key))
+            .collect::<Vec<_>>()
+            .join("/");
+        status::register(id, name, "按键");
+        suppressed.extend(
+            triggers
+                .iter()
+                .filter(|(t, _)| t.suppress)
+                .map(|(t, _)| t.code),
+        );
+        trigger_type(id, e.clone());
     }
+    set_suppressed(suppressed);
+    Ok(())
+}
+
+/**
+ * 按规则的坐标记录方式换算为当前屏幕上的坐标
+ * 相对窗口的坐标保持不变, 执行时按窗口当前的位置换算
+ */
+fn resolve_coordinates(e: &Elements) -> Result<Elements, String> {
+    let mut e = e.clone();
+    let space = match &e.coordinates {
+        None | Some(CoordinateSpace::Window { .. }) => return Ok(e),
+        Some(space) => space.clone(),
+    };
+    let screen_size = (
+        *SCREEN_WIDTH.lock().unwrap(),
+        *SCREEN_HEIGHT.lock().unwrap(),
+    );
+    let monitors = match space {
+        CoordinateSpace::Monitor { .. } => screen()?.monitors()?,
+        _ => Vec::new(),
+    };
+    let t = transform_for(&space, screen_size, &monitors, window_client_rect)?;
+    transform_elements(&mut e, &t);
+    Ok(e)
 }
 
 /**
@@ -60,33 +157,13 @@ pub fn close() {
         let mut stop_action = STOP_ACTION.lock().unwrap();
         *stop_action = true;
     }
+    // 关闭后触发键恢复传给游戏
+    set_suppressed(HashSet::new());
 }
 
 /**
- * 停止所有操作
+ * 监听暂停/恢复等全局快捷键, 快捷键在用户配置中设置
  */
 pub fn stop() {
-    thread::spawn(move || loop {
-        let mode_close = {
-            let mode_close = MODE_CLOSE.lock().unwrap();
-            *mode_close
-        };
-        if !mode_close {
-            let device_state = DeviceState::new();
-            let keys: Vec<Keycode> = device_state.get_keys();
-            if keys.len() > 0 && keys[0] == Keycode::F2 {
-                {
-                    let mut stop_action = STOP_ACTION.lock().unwrap();
-                    *stop_action = true;
-                }
-            }
-            if keys.len() > 0 && keys[0] == Keycode::F3 {
-                {
-                    let mut stop_action = STOP_ACTION.lock().unwrap();
-                    *stop_action = false;
-                }
-            }
-        }
-        std::thread::sleep(std::time::Duration::from_millis(*TIME_WITE));
-    });
+    listen_hotkeys(load_hotkeys());
 }
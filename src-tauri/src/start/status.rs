@@ -0,0 +1,88 @@
+use std::{collections::BTreeMap, sync::Mutex, time::Instant};
+
+use lazy_static::lazy_static;
+use serde::Serialize;
+
+use crate::global::global::STOP_ACTION;
+
+/// 单条规则的运行状态
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleStatus {
+    pub name: String,             // 规则名称
+    pub trigger: String,          // 触发方式
+    pub running: bool,            // 流程是否正在执行
+    pub runs: u64,                // 已执行次数
+    pub next_run_ms: Option<u64>, // 距离下次定时执行的时间ms
+}
+
+/// 运行时状态
+#[derive(Debug, Clone, Serialize)]
+pub struct RuntimeStatus {
+    pub paused: bool,
+    pub rules: Vec<RuleStatus>,
+}
+
+struct RuleState {
+    status: RuleStatus,
+    next_run: Option<Instant>,
+}
+
+lazy_static! {
+    static ref RULES: Mutex<BTreeMap<usize, RuleState>> = Mutex::new(BTreeMap::new());
+}
+
+pub fn register(id: usize, name: String, trigger: &str) {
+    RULES.lock().unwrap().insert(
+        id,
+        RuleState {
+            status: RuleStatus {
+                name,
+                trigger: trigger.to_string(),
+                running: false,
+                runs: 0,
+                next_run_ms: None,
+            },
+            next_run: None,
+        },
+    );
+}
+
+pub fn clear() {
+    RULES.lock().unwrap().clear();
+}
+
+/// 流程开始或结束执行
+pub fn set_running(id: usize, running: bool) {
+    if let Some(rule) = RULES.lock().unwrap().get_mut(&id) {
+        if running && !rule.status.running {
+            rule.status.runs += 1;
+        }
+        rule.status.running = running;
+    }
+}
+
+pub fn set_next_run(id: usize, next_run: Option<Instant>) {
+    if let Some(rule) = RULES.lock().unwrap().get_mut(&id) {
+        rule.next_run = next_run;
+    }
+}
+
+pub fn snapshot() -> RuntimeStatus {
+    let now = Instant::now();
+    let rules = RULES
+        .lock()
+        .unwrap()
+        .values()
+        .map(|rule| {
+            let mut status = rule.status.clone();
+            status.next_run_ms = rule
+                .next_run
+                .map(|next| next.saturating_duration_since(now).as_millis() as u64);
+            status
+        })
+        .collect();
+    RuntimeStatus {
+        paused: *STOP_ACTION.lock().unwrap(),
+        rules,
+    }
+}
@@ -0,0 +1,292 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicBool, AtomicU32, Ordering},
+            mpsc::channel,
+            Arc, Mutex,
+        },
+        time::{Duration, Instant},
+    };
+
+    use crate::memory::{
+        attach::{AttachState, Attachment},
+        process::{MemoryError, ProcessMemory, TargetProcess},
+    };
+
+    struct FakeProcess {
+        pid: u32,
+        alive: Arc<AtomicBool>,
+    }
+
+    impl ProcessMemory for FakeProcess {
+        fn read_bytes(&self, _address: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
+            Ok(vec![0; size])
+        }
+
+        fn module_base(&self, _module: Option<&str>) -> Result<usize, MemoryError> {
+            Ok(0)
+        }
+
+        fn pid(&self) -> u32 {
+            self.pid
+        }
+
+        fn is_alive(&self) -> bool {
+            self.alive.load(Ordering::SeqCst)
+        }
+    }
+
+    // 模拟的游戏进程, running 为正在运行的进程ID, 0 表示没有运行, 99 表示没有权限打开
+    #[derive(Default)]
+    struct Game {
+        running: AtomicU32,
+        alive: Mutex<Arc<AtomicBool>>,
+        opens: AtomicU32,
+    }
+
+    impl Game {
+        // 启动新的进程, 之前的进程退出
+        fn start(&self, pid: u32) {
+            let mut alive = self.alive.lock().unwrap();
+            alive.store(false, Ordering::SeqCst);
+            *alive = Arc::new(AtomicBool::new(pid != 0));
+            self.running.store(pid, Ordering::SeqCst);
+        }
+    }
+
+    fn attachment(game: &Arc<Game>) -> Attachment {
+        let game = game.clone();
+        Attachment::new(
+            TargetProcess::new("Client.exe"),
+            0x400000,
+            Duration::from_secs(1),
+            move |target, base| {
+                assert_eq!(base, 0x400000);
+                game.opens.fetch_add(1, Ordering::SeqCst);
+                match game.running.load(Ordering::SeqCst) {
+                    _ if target.name != "Client.exe" => Err(MemoryError::ProcessNotFound),
+                    // 选择的进程ID没有运行
+                    running if target.pid.is_some_and(|pid| pid != running) => {
+                        Err(MemoryError::ProcessNotFound)
+                    }
+                    0 => Err(MemoryError::ProcessNotFound),
+                    99 => Err(MemoryError::OpenProcessFailed("拒绝访问".to_string())),
+                    pid => Ok(Box::new(FakeProcess {
+                        pid,
+                        alive: game.alive.lock().unwrap().clone(),
+                    })),
+                }
+            },
+        )
+    }
+
+    #[test]
+    fn reconnect() {
+        let game = Arc::new(Game::default());
+        let attachment = attachment(&game);
+        let states = Arc::new(Mutex::new(Vec::new()));
+        let recorded = states.clone();
+        attachment.on_change(move |state| recorded.lock().unwrap().push(state.clone()));
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let opens = || game.opens.load(Ordering::SeqCst);
+
+        assert_eq!(attachment.state(), AttachState::Detached);
+        assert!(matches!(
+            attachment.process_at(at(0)),
+            Err(MemoryError::ProcessNotFound)
+        ));
+        assert_eq!(attachment.state(), AttachState::NotFound);
+        // 间隔内不重新查找进程
+        game.start(100);
+        assert!(attachment.process_at(at(500)).is_err());
+        assert_eq!(opens(), 1);
+
+        let process = attachment.process_at(at(1000)).unwrap();
+        assert_eq!(process.pid(), 100);
+        assert_eq!(attachment.state(), AttachState::Attached { pid: 100 });
+        // 连接一直复用
+        let again = attachment.process_at(at(1100)).unwrap();
+        assert!(Arc::ptr_eq(&process, &again));
+        assert_eq!(opens(), 2);
+
+        // 进程重启后立即重新连接
+        game.start(200);
+        assert!(!process.is_alive());
+        assert_eq!(attachment.process_at(at(1200)).unwrap().pid(), 200);
+        assert_eq!(opens(), 3);
+
+        // 进程退出
+        game.start(0);
+        assert!(attachment.process_at(at(1300)).is_err());
+        assert_eq!(attachment.state(), AttachState::NotFound);
+        assert_eq!(
+            *states.lock().unwrap(),
+            vec![
+                AttachState::NotFound,
+                AttachState::Attached { pid: 100 },
+                AttachState::Attached { pid: 200 },
+                AttachState::NotFound,
+            ]
+        );
+
+        // 打开失败时记录原因
+        game.start(99);
+        assert!(attachment.process_at(at(2300)).is_err());
+        assert!(matches!(
+            attachment.process_at(at(2400)),
+            Err(MemoryError::OpenProcessFailed(_))
+        ));
+        match attachment.state() {
+            AttachState::Failed { error } => assert!(error.contains("拒绝访问")),
+            state => panic!("{:?}", state),
+        }
+        assert_eq!(opens(), 5);
+    }
+
+    #[test]
+    fn change_target() {
+        let game = Arc::new(Game::default());
+        game.start(100);
+        let attachment = attachment(&game);
+        let states = Arc::new(Mutex::new(Vec::new()));
+        let recorded = states.clone();
+        attachment.on_change(move |state| recorded.lock().unwrap().push(state.clone()));
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        assert_eq!(attachment.process_at(at(0)).unwrap().pid(), 100);
+        // 更换目标后断开连接, 立即连接新的目标
+        attachment.set_target(TargetProcess::new("Other.exe"));
+        assert_eq!(attachment.state(), AttachState::Detached);
+        assert_eq!(attachment.target_process().name, "Other.exe");
+        assert!(attachment.process_at(at(100)).is_err());
+        // 选择的进程ID没有运行
+        attachment.set_target(TargetProcess {
+            name: "Client.exe".to_string(),
+            pid: Some(200),
+        });
+        assert!(attachment.process_at(at(200)).is_err());
+        attachment.set_target(TargetProcess {
+            name: "Client.exe".to_string(),
+            pid: Some(100),
+        });
+        assert_eq!(attachment.process_at(at(300)).unwrap().pid(), 100);
+        assert_eq!(
+            *states.lock().unwrap(),
+            vec![
+                AttachState::Attached { pid: 100 },
+                AttachState::Detached,
+                AttachState::NotFound,
+                AttachState::Detached,
+                AttachState::NotFound,
+                AttachState::Detached,
+                AttachState::Attached { pid: 100 },
+            ]
+        );
+    }
+
+    #[test]
+    fn pinned_pid_exits() {
+        let game = Arc::new(Game::default());
+        game.start(100);
+        let attachment = attachment(&game);
+        attachment.set_target(TargetProcess {
+            name: "Client.exe".to_string(),
+            pid: Some(100),
+        });
+        assert_eq!(attachment.process().unwrap().pid(), 100);
+        // 选择的进程退出后不再限定进程ID, 重启的客户端可以连接
+        game.start(200);
+        assert_eq!(attachment.process().unwrap().pid(), 200);
+        assert_eq!(attachment.target_process().pid, None);
+
+        // 选择的进程ID没有运行时也改为连接同名的进程
+        attachment.set_target(TargetProcess {
+            name: "Client.exe".to_string(),
+            pid: Some(100),
+        });
+        assert!(attachment.process().is_err());
+        assert_eq!(attachment.target_process().pid, None);
+        assert_eq!(attachment.process().unwrap().pid(), 200);
+    }
+
+    #[test]
+    fn poll_exit() {
+        let game = Arc::new(Game::default());
+        let attachment = attachment(&game);
+        let states = Arc::new(Mutex::new(Vec::new()));
+        let recorded = states.clone();
+        attachment.on_change(move |state| recorded.lock().unwrap().push(state.clone()));
+
+        // 没有连接时不查找进程
+        attachment.check_alive();
+        game.start(100);
+        assert_eq!(attachment.attached_pid(), None);
+        assert_eq!(game.opens.load(Ordering::SeqCst), 0);
+        attachment.process().unwrap();
+        attachment.check_alive();
+        assert_eq!(attachment.attached_pid(), Some(100));
+        assert_eq!(game.opens.load(Ordering::SeqCst), 1);
+        // 没有规则使用连接时进程退出, 也推送状态变化
+        game.start(0);
+        assert_eq!(attachment.attached_pid(), None);
+        attachment.check_alive();
+        assert_eq!(attachment.state(), AttachState::NotFound);
+        assert_eq!(
+            *states.lock().unwrap(),
+            vec![AttachState::Attached { pid: 100 }, AttachState::NotFound]
+        );
+    }
+
+    #[test]
+    fn open_without_lock() {
+        let (opening, started) = channel();
+        let (release, wait) = channel::<()>();
+        let wait = Mutex::new(wait);
+        let attachment = Arc::new(Attachment::new(
+            TargetProcess::new("Client.exe"),
+            0x400000,
+            Duration::from_secs(1),
+            move |_, _| {
+                opening.send(()).unwrap();
+                wait.lock().unwrap().recv().unwrap();
+                let process: Box<dyn ProcessMemory> = Box::new(FakeProcess {
+                    pid: 100,
+                    alive: Arc::new(AtomicBool::new(true)),
+                });
+                Ok(process)
+            },
+        ));
+        let connecting = attachment.clone();
+        let handle = std::thread::spawn(move || connecting.process().map(|p| p.pid()));
+        started.recv().unwrap();
+        // 打开进程期间可以读取状态和更换目标
+        assert_eq!(attachment.state(), AttachState::Detached);
+        attachment.set_target(TargetProcess::new("Other.exe"));
+        release.send(()).unwrap();
+        // 打开的是之前的目标, 结果被丢弃
+        assert!(handle.join().unwrap().is_err());
+        assert_eq!(attachment.state(), AttachState::Detached);
+
+        let connecting = attachment.clone();
+        let handle = std::thread::spawn(move || connecting.process().map(|p| p.pid()));
+        started.recv().unwrap();
+        release.send(()).unwrap();
+        assert_eq!(handle.join().unwrap().unwrap(), 100);
+        assert_eq!(attachment.state(), AttachState::Attached { pid: 100 });
+    }
+
+    #[test]
+    fn serialize_state() {
+        assert_eq!(
+            serde_json::to_value(AttachState::Attached { pid: 5 }).unwrap(),
+            serde_json::json!({"state": "attached", "pid": 5})
+        );
+        assert_eq!(
+            serde_json::to_value(AttachState::NotFound).unwrap(),
+            serde_json::json!({"state": "not_found"})
+        );
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use image::{Rgb, RgbImage};
+
+    use crate::{
+        global::model::{Bar, BarDirection, ElementEnum},
+        rgb::{bar::BarGauge, file::ImageSource, source::ScreenSource},
+    };
+
+    fn parse(json: &str) -> Bar {
+        match serde_json::from_str(json).unwrap() {
+            ElementEnum::Bar(b) => b,
+            other => panic!("not a bar: {:?}", other),
+        }
+    }
+
+    // 100×10 的血条, 填充部分红色, 空的部分深灰色
+    // 填充部分中间有白色数字, 空的部分有一个杂点
+    fn fixture(filled: u32, direction: BarDirection) -> ImageSource {
+        let image = RgbImage::from_fn(100, 10, |x, y| {
+            let is_filled = match direction {
+                BarDirection::LeftToRight => x < filled,
+                BarDirection::RightToLeft => x >= 100 - filled,
+            };
+            if (45..55).contains(&x) && (3..7).contains(&y) {
+                Rgb([255, 255, 255])
+            } else if is_filled || (x, y) == (90, 5) || (x, y) == (5, 5) {
+                Rgb([200, 30, 30])
+            } else {
+                Rgb([40, 40, 40])
+            }
+        });
+        ImageSource::from_image(image)
+    }
+
+    fn fill(json: &str, source: &ImageSource) -> f64 {
+        let gauge = BarGauge::parse(&parse(json)).unwrap();
+        let frame = source.region(0, 0, 100, 10).unwrap();
+        gauge.fill(|x, y| frame.pixel(x, y))
+    }
+
+    #[test]
+    fn left_to_right() {
+        let json = r#"{"bar": "0,0,100,10", "rgb": "205,30,30", "tolerance": 10, "below": 30}"#;
+        assert_eq!(fill(json, &fixture(60, BarDirection::LeftToRight)), 60.0);
+        // 数字遮住的部分和空的部分的杂点不影响结果
+        assert_eq!(fill(json, &fixture(30, BarDirection::LeftToRight)), 30.0);
+        assert_eq!(fill(json, &fixture(100, BarDirection::LeftToRight)), 100.0);
+        assert_eq!(fill(json, &fixture(0, BarDirection::LeftToRight)), 0.0);
+
+        // 只看血条的一部分
+        let json = r#"{"bar": "0,2,50,6", "rgb": "200,30,30", "below": 30}"#;
+        assert_eq!(fill(json, &fixture(20, BarDirection::LeftToRight)), 40.0);
+    }
+
+    #[test]
+    fn right_to_left() {
+        let json = r#"{"bar": "0,0,100,10", "rgb": "200,30,30", "direction": "right_to_left", "above": 50}"#;
+        assert_eq!(fill(json, &fixture(75, BarDirection::RightToLeft)), 75.0);
+        assert_eq!(fill(json, &fixture(0, BarDirection::RightToLeft)), 0.0);
+        // 方向设置错误时按最远的已填充列计算
+        let json = r#"{"bar": "0,0,100,10", "rgb": "200,30,30", "above": 50}"#;
+        assert_eq!(fill(json, &fixture(75, BarDirection::RightToLeft)), 100.0);
+    }
+
+    #[test]
+    fn threshold() {
+        let source = fixture(25, BarDirection::LeftToRight);
+        let frame = source.region(0, 0, 100, 10).unwrap();
+        let check = |json: &str| {
+            BarGauge::parse(&parse(json))
+                .unwrap()
+                .check(|x, y| frame.pixel(x, y))
+        };
+        assert!(check(
+            r#"{"bar": "0,0,100,10", "rgb": "200,30,30", "below": 30}"#
+        ));
+        assert!(!check(
+            r#"{"bar": "0,0,100,10", "rgb": "200,30,30", "below": 25}"#
+        ));
+        assert!(check(
+            r#"{"bar": "0,0,100,10", "rgb": "200,30,30", "above": 20}"#
+        ));
+        assert!(!check(
+            r#"{"bar": "0,0,100,10", "rgb": "200,30,30", "above": 20, "below": 25}"#
+        ));
+        // 颜色不匹配时填充为0
+        assert!(check(
+            r#"{"bar": "0,0,100,10", "rgb": "30,30,200", "below": 1}"#
+        ));
+    }
+
+    #[test]
+    fn invalid() {
+        let gauge = |json: &str| BarGauge::parse(&parse(json));
+        assert!(gauge(r#"{"bar": "0,0,100", "rgb": "200,30,30", "below": 30}"#).is_err());
+        assert!(gauge(r#"{"bar": "0,0,100,10", "rgb": "red", "below": 30}"#).is_err());
+        assert!(gauge(r#"{"bar": "0,0,100,10", "rgb": "200,30,30"}"#).is_err());
+        let gauge = gauge(r#"{"bar": "10,20,100,10", "rgb": "200,30,30", "below": 30}"#).unwrap();
+        assert_eq!(gauge.corners(), [(10, 20), (109, 29)]);
+        assert_eq!(gauge.direction, BarDirection::LeftToRight);
+    }
+}
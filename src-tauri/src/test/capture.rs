@@ -0,0 +1,189 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::{Duration, Instant},
+    };
+
+    use crate::{
+        act::context::color_points,
+        global::{common::RGB, model::Children},
+        rgb::{
+            capture::{bounding_region, FrameCache},
+            monitor::Monitor,
+            source::{Frame, ScreenSource},
+        },
+    };
+
+    // 颜色为 (x, y, 截图次数) 的虚拟屏幕, 记录截图次数
+    struct CountingSource {
+        captures: AtomicUsize,
+    }
+
+    impl ScreenSource for CountingSource {
+        fn region(&self, x: i32, y: i32, width: u32, height: u32) -> Result<Frame, String> {
+            let n = self.captures.fetch_add(1, Ordering::Relaxed) as u8 + 1;
+            let mut pixels = Vec::new();
+            for py in y..y + height as i32 {
+                for px in x..x + width as i32 {
+                    pixels.push(RGB::new(px as u8, py as u8, n));
+                }
+            }
+            Ok(Frame::new(x, y, width, height, pixels))
+        }
+
+        fn size(&self) -> Result<(u32, u32), String> {
+            Ok((100, 100))
+        }
+    }
+
+    fn source() -> CountingSource {
+        CountingSource {
+            captures: AtomicUsize::new(0),
+        }
+    }
+
+    #[test]
+    fn points_and_region() {
+        let children: Vec<Children> = serde_json::from_str(
+            r#"[
+                {"element": {"coordinate": "10,20", "rgb": "1,2,3"}, "iyn": "y", "children": [
+                    {"element": {"elements_key": "Q", "elements_code": 81, "key_up_delay": 0}, "iyn": "y", "children": null},
+                    {"element": {"coordinate": "30, 5", "rgb": "1,2,3"}, "iyn": "n", "children": null}
+                ]},
+                {"element": {"coordinate": "bad", "rgb": "1,2,3"}, "iyn": "y", "children": null}
+            ]"#,
+        )
+        .unwrap();
+        let points = color_points(&children);
+        assert_eq!(points, vec![(10, 20), (30, 5)]);
+        assert_eq!(bounding_region(&points), Some((10, 5, 21, 16)));
+        assert_eq!(bounding_region(&[]), None);
+    }
+
+    #[test]
+    fn one_capture_per_cycle() {
+        let source = source();
+        let now = Instant::now();
+        let mut frames = FrameCache::new(&[(10, 20), (30, 5)], None);
+
+        frames.begin_cycle();
+        let a = frames.pixel(&source, 10, 20, now).unwrap();
+        let b = frames.pixel(&source, 30, 5, now).unwrap();
+        assert_eq!(source.captures.load(Ordering::Relaxed), 1);
+        // 同一轮读取的是同一张截图
+        assert_eq!((a, b), (RGB::new(10, 20, 1), RGB::new(30, 5, 1)));
+
+        frames.begin_cycle();
+        let a = frames.pixel(&source, 10, 20, now).unwrap();
+        assert_eq!(a, RGB::new(10, 20, 2));
+        assert_eq!(source.captures.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn reuse_until_max_age() {
+        let source = source();
+        let start = Instant::now();
+        let mut frames = FrameCache::new(&[(1, 1)], Some(Duration::from_millis(100)));
+
+        frames.begin_cycle();
+        frames.pixel(&source, 1, 1, start).unwrap();
+        frames.begin_cycle();
+        frames
+            .pixel(&source, 1, 1, start + Duration::from_millis(100))
+            .unwrap();
+        assert_eq!(source.captures.load(Ordering::Relaxed), 1);
+
+        let rgb = frames
+            .pixel(&source, 1, 1, start + Duration::from_millis(101))
+            .unwrap();
+        assert_eq!(rgb, RGB::new(1, 1, 2));
+    }
+
+    #[test]
+    fn outside_region_reads_directly() {
+        let source = source();
+        let mut frames = FrameCache::new(&[(1, 1)], None);
+        let rgb = frames.pixel(&source, 50, 50, Instant::now()).unwrap();
+        assert_eq!(rgb, RGB::new(50, 50, 2));
+        // 没有取色坐标时直接读取
+        let mut empty = FrameCache::new(&[], None);
+        empty.pixel(&source, 3, 3, Instant::now()).unwrap();
+        assert_eq!(source.captures.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn widen_for_captures() {
+        let source = source();
+        let now = Instant::now();
+        let mut frames = FrameCache::new(&[(10, 20), (30, 5)], None);
+        // 坐标周围的区域超出屏幕的部分不截取
+        frames.widen((-10, 10, 40, 40), (0, 0, 100, 100));
+        let frame = frames.frame(&source, now).unwrap().unwrap();
+        assert_eq!(
+            (frame.x, frame.y, frame.width, frame.height),
+            (0, 5, 31, 45)
+        );
+        // 完全在屏幕外时不改变区域
+        frames.widen((200, 200, 10, 10), (0, 0, 100, 100));
+        let frame = frames.frame(&source, now).unwrap().unwrap();
+        assert_eq!((frame.width, frame.height), (31, 45));
+        assert_eq!(source.captures.load(Ordering::Relaxed), 1);
+
+        let mut empty = FrameCache::new(&[], None);
+        empty.widen((90, 90, 20, 20), (0, 0, 100, 100));
+        let frame = empty.frame(&source, now).unwrap().unwrap();
+        assert_eq!(
+            (frame.x, frame.y, frame.width, frame.height),
+            (90, 90, 10, 10)
+        );
+    }
+
+    // 两个显示器之间有空隙, 截图区域包含空隙时失败
+    struct SplitSource;
+
+    impl ScreenSource for SplitSource {
+        fn region(&self, x: i32, y: i32, width: u32, height: u32) -> Result<Frame, String> {
+            if x < 100 && x + width as i32 > 100 || (100..200).contains(&x) {
+                return Err(format!(
+                    "区域不在显示器内: {},{} {}x{}",
+                    x, y, width, height
+                ));
+            }
+            let pixels = vec![RGB::new(1, 2, 3); (width * height) as usize];
+            Ok(Frame::new(x, y, width, height, pixels))
+        }
+
+        fn size(&self) -> Result<(u32, u32), String> {
+            Ok((100, 100))
+        }
+
+        fn monitors(&self) -> Result<Vec<Monitor>, String> {
+            Ok([0, 200]
+                .into_iter()
+                .enumerate()
+                .map(|(i, x)| Monitor {
+                    index: i + 1,
+                    name: format!("monitor{}", i + 1),
+                    x,
+                    y: 0,
+                    width: 100,
+                    height: 100,
+                    primary: x == 0,
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn offscreen_node_reads_alone() {
+        let now = Instant::now();
+        // 坐标在显示器之间的空隙时截图失败, 其他坐标单独读取
+        let mut frames = FrameCache::new(&[(50, 50), (150, 50)], None);
+        assert_eq!(
+            frames.pixel(&SplitSource, 50, 50, now),
+            Ok(RGB::new(1, 2, 3))
+        );
+        assert!(frames.pixel(&SplitSource, 150, 50, now).is_err());
+    }
+}
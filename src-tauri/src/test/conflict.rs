@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        global::model::{Element, Elements, Timer, Trigger, TriggerMode},
+        start::{
+            conflict::{detect_conflicts, hotkey_triggers, rule_triggers},
+            hotkey::{HotKeyConfig, HotKeys},
+        },
+    };
+
+    fn rule(code: u32, key: &str) -> Elements {
+        Elements {
+            header: Element {
+                elements_key: key.to_string(),
+                elements_code: code,
+                key_up_delay: 0,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn same_key() {
+        let elements = vec![rule(81, "Q"), rule(87, "W"), rule(81, "Q")];
+        let conflicts = detect_conflicts(&rule_triggers(&elements, 1));
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].first, "规则1(Q)");
+        assert_eq!(conflicts[0].second, "规则3(Q)");
+        assert_eq!(conflicts[0].reason, "触发键相同");
+    }
+
+    #[test]
+    fn same_key_different_mode() {
+        let mut tap = rule(81, "Q");
+        tap.tap_children = Some(Vec::new());
+        let conflicts = detect_conflicts(&rule_triggers(&[rule(81, "Q"), tap], 1));
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].reason.contains("模式不同"));
+    }
+
+    #[test]
+    fn hotkey_contains_rule_key() {
+        let hotkeys = HotKeys::from_config(&HotKeyConfig::default()).unwrap();
+        // Ctrl+F12 包含 F12, Ctrl 作为触发键也会被 Ctrl+F12 触发
+        let mut triggers = rule_triggers(&[rule(123, "F12"), rule(17, "Ctrl")], 1);
+        triggers.extend(hotkey_triggers(&hotkeys));
+        let conflicts = detect_conflicts(&triggers);
+        assert_eq!(conflicts.len(), 2);
+        assert!(conflicts.iter().all(|c| c.reason.starts_with("按下 Ctrl+F12")));
+        assert_eq!(conflicts[0].key, "F12");
+        assert_eq!(conflicts[1].key, "Ctrl");
+
+        // F2 与暂停快捷键相同
+        let mut triggers = rule_triggers(&[rule(113, "F2")], 1);
+        triggers.extend(hotkey_triggers(&hotkeys));
+        let conflicts = detect_conflicts(&triggers);
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].reason.contains("模式不同"));
+    }
+
+    #[test]
+    fn skip_timer_and_unsupported() {
+        let mut timer = rule(81, "Q");
+        timer.timer = Some(Timer {
+            interval: Some(1000),
+            delay: None,
+        });
+        let elements = vec![rule(81, "Q"), timer, rule(9999, "?"), rule(9999, "?")];
+        assert!(detect_conflicts(&rule_triggers(&elements, 1)).is_empty());
+    }
+
+    #[test]
+    fn multiple_triggers() {
+        let mut g = rule(71, "G");
+        g.triggers = Some(vec![
+            serde_json::from_str(r#"{"code": 71}"#).unwrap(),
+            serde_json::from_str(r#"{"code": 5, "mode": "click", "auto_off": 10}"#).unwrap(),
+        ]);
+        let triggers = rule_triggers(&[g.clone()], 1);
+        assert_eq!(triggers.len(), 2);
+        assert_eq!(triggers[0].mode, "长按");
+        assert_eq!(triggers[1].mode, "单击");
+        // 同一条规则的触发键之间不冲突
+        assert!(detect_conflicts(&triggers).is_empty());
+
+        let mut x2 = rule(81, "Q");
+        x2.triggers = Some(vec![Trigger {
+            code: 5,
+            mode: Some(TriggerMode::DoubleTap),
+            auto_off: None,
+            window: None,
+            suppress: false,
+        }]);
+        let conflicts = detect_conflicts(&rule_triggers(&[g, x2], 1));
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].first, "规则1(侧上键)");
+        assert_eq!(conflicts[0].reason, "触发键相同但模式不同(单击 / 双击)");
+    }
+}
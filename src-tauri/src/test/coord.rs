@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        global::model::{Children, CoordinateSpace, ElementEnum, Elements},
+        rgb::coord::{transform_elements, transform_for, Transform, WindowTracker},
+    };
+
+    fn no_window(title: &str) -> Result<(i32, i32, u32, u32), String> {
+        Err(format!("找不到窗口: {}", title))
+    }
+
+    fn node(json: &str) -> Children {
+        Children {
+            element: serde_json::from_str(json).unwrap(),
+            iyn: String::new(),
+            children: None,
+        }
+    }
+
+    #[test]
+    fn reference_resolution() {
+        let space = CoordinateSpace::Reference {
+            width: 1920,
+            height: 1080,
+        };
+        let t = transform_for(&space, (2560, 1440), &[], no_window).unwrap();
+        assert_eq!(t.point(960, 540), (1280, 720));
+        assert_eq!(t.point(1919, 1079), (2559, 1439));
+        assert_eq!(t.offset(3, -3), (4, -4));
+        assert_eq!(t.region((100, 100, 30, 30)), (133, 133, 40, 40));
+
+        // 缩小时区域至少保留 1 个像素
+        let t = transform_for(&space, (1280, 720), &[], no_window).unwrap();
+        assert_eq!(t.point(1920, 1080), (1280, 720));
+        assert_eq!(t.region((0, 0, 1, 1)), (0, 0, 1, 1));
+
+        assert!(transform_for(&space, (0, 0), &[], no_window).is_err());
+        assert!(
+            transform_for(&CoordinateSpace::Screen, (0, 0), &[], no_window)
+                .unwrap()
+                .is_identity()
+        );
+    }
+
+    #[test]
+    fn window_client_area() {
+        let window = |title: &str| match title {
+            "游戏" => Ok((200, 100, 1600, 900)),
+            _ => no_window(title),
+        };
+        // 只记录相对位置时只平移
+        let space = CoordinateSpace::Window {
+            title: "游戏".to_string(),
+            width: None,
+            height: None,
+        };
+        let t = transform_for(&space, (2560, 1440), &[], window).unwrap();
+        assert_eq!(t.point(10, 20), (210, 120));
+        assert_eq!(t.offset(5, 5), (5, 5));
+
+        // 记录了客户区大小时按当前大小缩放
+        let space = CoordinateSpace::Window {
+            title: "游戏".to_string(),
+            width: Some(800),
+            height: Some(450),
+        };
+        let t = transform_for(&space, (2560, 1440), &[], window).unwrap();
+        assert_eq!(t.point(400, 225), (1000, 550));
+
+        let missing = CoordinateSpace::Window {
+            title: "记事本".to_string(),
+            width: None,
+            height: None,
+        };
+        assert!(transform_for(&missing, (2560, 1440), &[], window).is_err());
+    }
+
+    #[test]
+    fn window_moves() {
+        let rect = std::cell::Cell::new(Some((200, 100, 1600, 900)));
+        let window = |title: &str| rect.get().ok_or_else(|| format!("找不到窗口: {}", title));
+        let space = CoordinateSpace::Window {
+            title: "游戏".to_string(),
+            width: Some(800),
+            height: Some(450),
+        };
+        let mut tracker = WindowTracker::new(&space).unwrap();
+        let t = tracker.update(window).unwrap().unwrap();
+        assert_eq!(t.point(400, 225), (1000, 550));
+        // 窗口没有变化时不重新换算
+        assert_eq!(tracker.update(window).unwrap(), None);
+        // 窗口移动或改变大小后按新的位置换算
+        rect.set(Some((0, 0, 800, 450)));
+        let t = tracker.update(window).unwrap().unwrap();
+        assert_eq!(t.point(400, 225), (400, 225));
+        rect.set(None);
+        assert!(tracker.update(window).is_err());
+
+        // 其他方式在开启时已经换算
+        assert!(WindowTracker::new(&CoordinateSpace::Screen).is_none());
+    }
+
+    #[test]
+    fn rule_coordinates() {
+        let json = r#"{"type": "reference", "width": 1280, "height": 720}"#;
+        let space: CoordinateSpace = serde_json::from_str(json).unwrap();
+        let t = transform_for(&space, (1920, 1080), &[], no_window).unwrap();
+
+        let mut pattern = node(
+            r#"{"anchor": "100,50", "search": "0,0,200,100",
+                "points": [{"dx": 2, "dy": -2, "rgb": "0,0,0"}]}"#,
+        );
+        pattern.children = Some(vec![node(r#"{"coordinate": "10,10", "rgb": "1,2,3"}"#)]);
+        let mut e = Elements {
+            children: Some(vec![
+                pattern,
+                node(r#"{"template": "buff", "region": "10,10,20,20"}"#),
+                node(r#"{"mouse": "left", "position": "640,360"}"#),
+            ]),
+            coordinates: Some(space),
+            ..Default::default()
+        };
+        transform_elements(&mut e, &t);
+
+        let children = e.children.unwrap();
+        match &children[0].element {
+            ElementEnum::ColorPattern(cp) => {
+                assert_eq!(cp.anchor.as_deref(), Some("150,75"));
+                assert_eq!(cp.search.as_deref(), Some("0,0,300,150"));
+                assert_eq!((cp.points[0].dx, cp.points[0].dy), (3, -3));
+            }
+            other => panic!("not a color pattern: {:?}", other),
+        }
+        match &children[0].children.as_ref().unwrap()[0].element {
+            ElementEnum::Color(co) => assert_eq!(co.coordinate, "15,15"),
+            other => panic!("not a color: {:?}", other),
+        }
+        match &children[1].element {
+            ElementEnum::Template(tp) => assert_eq!(tp.region, "15,15,30,30"),
+            other => panic!("not a template: {:?}", other),
+        }
+        match &children[2].element {
+            ElementEnum::Mouse(m) => assert_eq!(m.position.as_deref(), Some("960,540")),
+            other => panic!("not a mouse: {:?}", other),
+        }
+        // 格式有误的坐标原样保留
+        assert_eq!(Transform::IDENTITY.coordinate("abc"), "abc");
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::{
+        act::context::capture_key,
+        global::{
+            common::RGB,
+            model::{CaptureMode, Color},
+        },
+        rgb::{
+            debug::{annotate, crop_around, prune, save_capture, CaptureConfig, CaptureTracker},
+            source::Frame,
+        },
+    };
+
+    // 左上角在 (100, 50) 的 40×30 截图, 颜色按坐标变化
+    fn frame() -> Frame {
+        let mut pixels = Vec::new();
+        for y in 0..30 {
+            for x in 0..40 {
+                pixels.push(RGB::new(x as u8, y as u8, 100));
+            }
+        }
+        Frame::new(100, 50, 40, 30, pixels)
+    }
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("ruby_captures_{}_{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn tracker() {
+        let mut tracker = CaptureTracker::default();
+        let start = Instant::now();
+        let interval = Duration::from_millis(100);
+        let at = |ms| start + Duration::from_millis(ms);
+
+        assert!(!tracker.record("a", CaptureMode::Off, true, at(0), interval));
+        // always: 第一次保存, 间隔内不重复保存
+        assert!(tracker.record("b", CaptureMode::Always, true, at(0), interval));
+        assert!(!tracker.record("b", CaptureMode::Always, false, at(50), interval));
+        assert!(tracker.record("b", CaptureMode::Always, false, at(100), interval));
+        // flip: 第一次只记录, 结果变化时保存, 不受间隔限制
+        assert!(!tracker.record("c", CaptureMode::Flip, true, at(0), interval));
+        assert!(!tracker.record("c", CaptureMode::Flip, true, at(10), interval));
+        assert!(tracker.record("c", CaptureMode::Flip, false, at(20), interval));
+        assert!(tracker.record("c", CaptureMode::Flip, true, at(30), interval));
+        // 不同节点分别记录
+        assert!(!tracker.record("d", CaptureMode::Flip, false, at(30), interval));
+    }
+
+    #[test]
+    fn crop_and_annotate() {
+        let frame = frame();
+        let cropped = crop_around(&frame, 120, 65, 10);
+        assert_eq!(
+            (cropped.x, cropped.y, cropped.width, cropped.height),
+            (115, 60, 10, 10)
+        );
+        assert_eq!(cropped.pixel(120, 65), frame.pixel(120, 65));
+        // 靠近边缘时不超出截图
+        let cropped = crop_around(&frame, 101, 78, 10);
+        assert_eq!((cropped.x, cropped.y), (100, 70));
+        // 截图比限制小时保持原样
+        let cropped = crop_around(&frame, 120, 65, 200);
+        assert_eq!(
+            (cropped.x, cropped.y, cropped.width, cropped.height),
+            (100, 50, 40, 30)
+        );
+
+        let image = annotate(&frame, &[(110, 60, true), (138, 78, false)]);
+        // 坐标本身不被覆盖, 周围画框
+        assert_eq!(image.get_pixel(10, 10).0, [10, 10, 100]);
+        assert_eq!(image.get_pixel(7, 10).0, [0, 255, 0]);
+        assert_eq!(image.get_pixel(13, 13).0, [0, 255, 0]);
+        assert_eq!(image.get_pixel(9, 9).0, [9, 9, 100]);
+        assert_eq!(image.get_pixel(35, 26).0, [255, 0, 255]);
+    }
+
+    #[test]
+    fn save_and_prune() {
+        let dir = temp_dir("save");
+        let config = CaptureConfig {
+            max_side: 16,
+            max_files: 3,
+            ..Default::default()
+        };
+        let mut paths = Vec::new();
+        for i in 0..5 {
+            let path =
+                save_capture(&dir, "120,65", &frame(), &[(120, 65, i % 2 == 0)], &config).unwrap();
+            paths.push(path);
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        let name = paths[4].file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.ends_with("_120,65_y.png"));
+        let image = image::open(&paths[4]).unwrap();
+        assert_eq!((image.width(), image.height()), (16, 16));
+        // 只保留最新的 3 张
+        assert!(!paths[0].exists() && !paths[1].exists());
+        assert!(paths[2..].iter().all(|p| p.exists()));
+
+        std::fs::write(dir.join("note.txt"), "x").unwrap();
+        let config = CaptureConfig {
+            max_bytes: 1,
+            ..config
+        };
+        assert_eq!(prune(&dir, &config).unwrap(), 3);
+        assert!(dir.join("note.txt").exists());
+        assert!(save_capture(&dir, "a", &frame(), &[], &config).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn key_by_node() {
+        let color = |node: Option<&str>| Color {
+            coordinate: "10,20".to_string(),
+            rgb: "1,2,3".to_string(),
+            node: node.map(str::to_string),
+            ..Default::default()
+        };
+        // 坐标和颜色相同的两个节点分别记录
+        assert_eq!(capture_key(&color(Some("a"))), "a");
+        assert_ne!(
+            capture_key(&color(Some("a"))),
+            capture_key(&color(Some("b")))
+        );
+        assert_eq!(capture_key(&color(None)), "10,20|1,2,3");
+    }
+
+    #[test]
+    fn concurrent_prune() {
+        let dir = temp_dir("concurrent");
+        std::fs::create_dir_all(&dir).unwrap();
+        for i in 0..40 {
+            std::fs::write(dir.join(format!("{:02}.png", i)), "x").unwrap();
+        }
+        let config = CaptureConfig {
+            max_files: 0,
+            ..Default::default()
+        };
+        // 其他线程已经删除的文件不算错误
+        let removed: usize = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| prune(&dir, &config).unwrap()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).sum()
+        });
+        assert_eq!(removed, 40);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod tests {
+    use image::{Rgb, RgbImage};
+
+    use crate::{
+        global::model::{Digits, ElementEnum},
+        rgb::{
+            digits::{number_holds, save_glyphs, DigitReader, GlyphSet, Mask},
+            file::ImageSource,
+            source::{Frame, ScreenSource},
+        },
+    };
+
+    // 3×5 点阵数字
+    const FONT: [[&str; 5]; 10] = [
+        ["###", "#.#", "#.#", "#.#", "###"],
+        [".#.", "##.", ".#.", ".#.", "###"],
+        ["###", "..#", "###", "#..", "###"],
+        ["###", "..#", "###", "..#", "###"],
+        ["#.#", "#.#", "###", "..#", "..#"],
+        ["###", "#..", "###", "..#", "###"],
+        ["###", "#..", "###", "#.#", "###"],
+        ["###", "..#", "..#", ".#.", ".#."],
+        ["###", "#.#", "###", "#.#", "###"],
+        ["###", "#.#", "###", "..#", "###"],
+    ];
+
+    const WHITE: Rgb<u8> = Rgb([250, 250, 250]);
+    const BACKGROUND: Rgb<u8> = Rgb([30, 40, 60]);
+
+    // 在 (2, 2) 开始用 scale 倍大小画出 text, 字间隔 2 个像素
+    // 背景上有一个和数字颜色不同的图标
+    fn draw(text: &str, scale: u32, color: Rgb<u8>) -> Frame {
+        let mut image = RgbImage::from_pixel(60, 20, BACKGROUND);
+        for (i, c) in text.chars().enumerate() {
+            let glyph = &FONT[c.to_digit(10).unwrap() as usize];
+            let left = 2 + i as u32 * (3 * scale + 2);
+            for (row, line) in glyph.iter().enumerate() {
+                for (col, _) in line.chars().enumerate().filter(|(_, p)| *p == '#') {
+                    for dy in 0..scale {
+                        for dx in 0..scale {
+                            let x = left + col as u32 * scale + dx;
+                            let y = 2 + row as u32 * scale + dy;
+                            image.put_pixel(x, y, color);
+                        }
+                    }
+                }
+            }
+        }
+        for y in 12..15 {
+            for x in 50..58 {
+                image.put_pixel(x, y, Rgb([200, 0, 0]));
+            }
+        }
+        ImageSource::from_image(image).region(0, 0, 60, 20).unwrap()
+    }
+
+    fn parse(json: &str) -> Digits {
+        match serde_json::from_str(json).unwrap() {
+            ElementEnum::Digits(d) => d,
+            other => panic!("not digits: {:?}", other),
+        }
+    }
+
+    // 用两张截图保存 0-9 的字形, 返回字形集目录
+    fn glyph_set(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("ruby_glyphs_{}_{}", std::process::id(), name));
+        let reader = DigitReader::parse(&parse(
+            r#"{"digits": "a", "region": "0,0,60,20", "rgb": "255,255,255", "tolerance": 10}"#,
+        ))
+        .unwrap();
+        save_glyphs(&dir, "01234", &reader.mask(&draw("01234", 2, WHITE))).unwrap();
+        save_glyphs(&dir, "56789", &reader.mask(&draw("56789", 2, WHITE))).unwrap();
+        dir
+    }
+
+    #[test]
+    fn segment_by_color() {
+        let frame = draw("120", 2, WHITE);
+        let reader = DigitReader::parse(&parse(
+            r#"{"digits": "a", "region": "0,0,60,20", "rgb": "250,250,250"}"#,
+        ))
+        .unwrap();
+        let glyphs = reader.mask(&frame).segment();
+        // 红色图标不是数字的颜色, 不参与分割
+        assert_eq!(glyphs.len(), 3);
+        assert_eq!((glyphs[0].width, glyphs[0].height), (6, 10));
+        assert!(!glyphs[0].get(0, 0) && glyphs[0].get(2, 0));
+
+        let empty = Mask {
+            width: 3,
+            height: 2,
+            bits: vec![false; 6],
+        };
+        assert!(empty.segment().is_empty());
+        assert!(empty.trim().is_none());
+    }
+
+    #[test]
+    fn read_numbers() {
+        let dir = glyph_set("read");
+        let glyphs = GlyphSet::open(&dir).unwrap();
+        assert_eq!(glyphs.glyphs.len(), 10);
+        let reader = DigitReader::parse(&parse(
+            r#"{"digits": "a", "region": "0,0,60,20", "rgb": "250,250,250", "tolerance": 20}"#,
+        ))
+        .unwrap();
+        for text in ["7", "10", "1207", "98", "3456"] {
+            let value = reader.read(&draw(text, 2, WHITE), &glyphs);
+            assert_eq!(value, Some(text.parse().unwrap()), "{}", text);
+        }
+        // 字体大小不同时按比例缩放后比较
+        assert_eq!(reader.read(&draw("258", 3, WHITE), &glyphs), Some(258));
+        // 区域内没有数字的颜色
+        assert_eq!(reader.read(&draw("12", 2, Rgb([0, 200, 0])), &glyphs), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unknown_glyph() {
+        let dir = glyph_set("unknown");
+        std::fs::remove_file(dir.join("8.png")).unwrap();
+        let glyphs = GlyphSet::open(&dir).unwrap();
+        let reader = DigitReader::parse(&parse(
+            r#"{"digits": "a", "region": "0,0,60,20", "rgb": "250,250,250", "min_score": 0.95}"#,
+        ))
+        .unwrap();
+        // 缺少字形的数字无法识别, 整个数字读取失败
+        assert_eq!(reader.read(&draw("18", 2, WHITE), &glyphs), None);
+        assert_eq!(reader.read(&draw("19", 2, WHITE), &glyphs), Some(19));
+
+        let mask = reader.mask(&draw("12", 2, WHITE));
+        assert!(save_glyphs(&dir, "123", &mask).is_err());
+        assert!(save_glyphs(&dir, "1a", &mask).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(GlyphSet::open(&dir).is_err());
+    }
+
+    #[test]
+    fn conditions() {
+        let d = parse(r#"{"digits": "a", "region": "0,0,6,6", "rgb": "1,1,1", "below": 3}"#);
+        assert!(number_holds(2, &d));
+        assert!(!number_holds(3, &d));
+        let d = parse(
+            r#"{"digits": "a", "region": "0,0,6,6", "rgb": "1,1,1", "above": 0, "below": 10}"#,
+        );
+        assert!(number_holds(5, &d));
+        assert!(!number_holds(0, &d));
+        let d = parse(r#"{"digits": "a", "region": "0,0,6,6", "rgb": "1,1,1", "equal": 5}"#);
+        assert!(number_holds(5, &d) && !number_holds(6, &d));
+        // 都没有设置时读取到数字即成立
+        let d = parse(r#"{"digits": "a", "region": "0,0,6,6", "rgb": "1,1,1"}"#);
+        assert!(number_holds(0, &d));
+        assert!(DigitReader::parse(&parse(
+            r#"{"digits": "a", "region": "0,0", "rgb": "1,1,1"}"#
+        ))
+        .is_err());
+    }
+}
@@ -0,0 +1,14 @@
+use image::{Rgb, RgbImage};
+
+use crate::rgb::file::ImageSource;
+
+/// 20×10 的画面, 左半边红色, 右半边蓝色
+pub fn red_blue() -> ImageSource {
+    ImageSource::from_image(RgbImage::from_fn(20, 10, |x, _| {
+        if x < 10 {
+            Rgb([200, 0, 0])
+        } else {
+            Rgb([0, 0, 200])
+        }
+    }))
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        time::{Duration, SystemTime},
+    };
+
+    use rdev::{Event, EventType, Key};
+
+    use crate::input::hook::{
+        add_subscriber, dispatch_with, is_held, mark_injected, replace_suppressed,
+    };
+
+    const F9: u32 = 120;
+
+    fn key(event_type: EventType) -> Event {
+        Event {
+            time: SystemTime::now(),
+            name: None,
+            event_type,
+        }
+    }
+
+    // 离开作用域时取消拦截, 断言失败时也不影响其他测试
+    struct Suppressed;
+
+    impl Drop for Suppressed {
+        fn drop(&mut self) {
+            replace_suppressed(HashSet::new());
+        }
+    }
+
+    // 没有暂停时分发事件
+    fn dispatch(event: &Event) -> bool {
+        dispatch_with(event, false)
+    }
+
+    #[test]
+    fn injected_events_pass_through() {
+        // 不安装系统钩子, 事件全部由测试分发
+        let events = add_subscriber();
+        replace_suppressed(HashSet::from([F9]));
+        let _suppressed = Suppressed;
+
+        // 程序自己发送的按键: 不拦截、不记录按住、不触发规则
+        mark_injected(F9, true);
+        mark_injected(F9, false);
+        assert!(!dispatch(&key(EventType::KeyPress(Key::F9))));
+        assert!(!is_held(F9));
+        assert!(!dispatch(&key(EventType::KeyRelease(Key::F9))));
+        assert!(events.recv_timeout(Duration::from_millis(50)).is_err());
+
+        // 真实按键照常拦截并触发
+        assert!(dispatch(&key(EventType::KeyPress(Key::F9))));
+        assert!(is_held(F9));
+        // 按住期间程序发送的抬起不会结束按住
+        mark_injected(F9, false);
+        assert!(!dispatch(&key(EventType::KeyRelease(Key::F9))));
+        assert!(is_held(F9));
+        assert!(dispatch(&key(EventType::KeyRelease(Key::F9))));
+        assert!(!is_held(F9));
+
+        // 暂停期间不拦截
+        assert!(!dispatch_with(&key(EventType::KeyPress(Key::F9)), true));
+        assert!(!dispatch_with(&key(EventType::KeyRelease(Key::F9)), true));
+
+        let pressed: Vec<bool> = std::iter::from_fn(|| {
+            events
+                .recv_timeout(Duration::from_millis(50))
+                .ok()
+                .filter(|e| e.code == F9)
+                .map(|e| e.pressed)
+        })
+        .collect();
+        assert_eq!(pressed, vec![true, false, true, false]);
+    }
+
+    #[test]
+    fn modifier_sides_held_separately() {
+        const CTRL: u32 = 17;
+        let events = add_subscriber();
+        assert!(!dispatch(&key(EventType::KeyPress(Key::ControlLeft))));
+        assert!(!dispatch(&key(EventType::KeyPress(Key::ControlRight))));
+        // 松开一侧时另一侧仍按住
+        dispatch(&key(EventType::KeyRelease(Key::ControlLeft)));
+        assert!(is_held(CTRL));
+        dispatch(&key(EventType::KeyRelease(Key::ControlRight)));
+        assert!(!is_held(CTRL));
+
+        let pressed: Vec<bool> =
+            std::iter::from_fn(|| events.recv_timeout(Duration::from_millis(50)).ok())
+                .filter(|e| e.code == CTRL)
+                .map(|e| e.pressed)
+                .collect();
+        assert_eq!(pressed, vec![true, false]);
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use crate::start::hotkey::{HotKey, HotKeyAction, HotKeyConfig, HotKeys};
+
+    #[test]
+    fn parse_combo() {
+        let key = HotKey::parse("Ctrl + shift + F2").unwrap();
+        assert_eq!(key.modifiers, vec![17, 16]);
+        assert_eq!(key.code, 113);
+        assert_eq!(key.to_string(), "Ctrl+Shift+F2");
+        assert_eq!(HotKey::parse("F2+Ctrl").unwrap().modifiers, vec![17]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(HotKey::parse("Ctrl+Foo").is_err());
+        assert!(HotKey::parse("F2+F3").is_err());
+        assert!(HotKey::parse("").is_err());
+    }
+
+    #[test]
+    fn modifiers_must_match_exactly() {
+        let key = HotKey::parse("Ctrl+F12").unwrap();
+        assert!(key.matches(123, |c| c == 17));
+        assert!(!key.matches(123, |_| false));
+        assert!(!key.matches(123, |c| c == 17 || c == 16));
+        // 按住其他普通按键不影响
+        let f2 = HotKey::parse("F2").unwrap();
+        assert!(f2.matches(113, |c| c == 87));
+    }
+
+    #[test]
+    fn config_actions() {
+        let hotkeys = HotKeys::from_config(&HotKeyConfig::default()).unwrap();
+        assert_eq!(hotkeys.action(113, |_| false), Some(HotKeyAction::Pause));
+        assert_eq!(hotkeys.action(114, |_| false), Some(HotKeyAction::Resume));
+        assert_eq!(
+            hotkeys.action(123, |c| c == 17),
+            Some(HotKeyAction::EmergencyStop)
+        );
+        assert_eq!(hotkeys.action(123, |_| false), None);
+
+        let config = HotKeyConfig {
+            pause: Some("F2".to_string()),
+            resume: Some("f2".to_string()),
+            toggle: None,
+            emergency_stop: None,
+        };
+        assert!(HotKeys::from_config(&config).is_err());
+    }
+}
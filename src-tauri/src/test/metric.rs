@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        global::{
+            common::RGB,
+            model::{ColorMetric, ElementEnum},
+        },
+        rgb::{
+            check::color_check_one_by_one,
+            metric::{ciede2000, color_distance, color_matches, suggest_tolerance},
+        },
+    };
+
+    #[test]
+    fn channel_max_and_euclidean() {
+        let a = RGB::new(100, 100, 100);
+        let b = RGB::new(103, 104, 100);
+        assert_eq!(color_distance(ColorMetric::ChannelMax, a, b), 4.0);
+        assert_eq!(color_distance(ColorMetric::Euclidean, a, b), 5.0);
+        assert!(color_matches(ColorMetric::ChannelMax, a, b, 4.0, None));
+        assert!(!color_matches(ColorMetric::Euclidean, a, b, 4.9, None));
+        // 旧的取色偏差保持原来的算法, 差值之和不超过偏差的 3 倍
+        assert!(color_check_one_by_one(a, b, 3));
+        assert!(!color_check_one_by_one(a, b, 2));
+        assert!(color_check_one_by_one(a, RGB::new(109, 100, 100), 3));
+    }
+
+    #[test]
+    fn hsv_ignores_brightness() {
+        let red = RGB::new(220, 30, 30);
+        let dark_red = RGB::new(110, 15, 15);
+        let orange = RGB::new(220, 120, 30);
+        assert!(color_matches(ColorMetric::Hsv, dark_red, red, 5.0, None));
+        assert!(!color_matches(ColorMetric::Hsv, orange, red, 5.0, None));
+        // 色相跨过 0 度
+        let magenta_red = RGB::new(220, 30, 45);
+        assert!(color_distance(ColorMetric::Hsv, magenta_red, red) < 5.0);
+        // 饱和度超出范围
+        let pale_red = RGB::new(220, 150, 150);
+        assert!(!color_matches(
+            ColorMetric::Hsv,
+            pale_red,
+            red,
+            5.0,
+            Some(0.1)
+        ));
+        assert!(color_matches(
+            ColorMetric::Hsv,
+            pale_red,
+            red,
+            5.0,
+            Some(0.6)
+        ));
+    }
+
+    #[test]
+    fn ciede2000_reference_pairs() {
+        // Sharma 等人给出的测试数据
+        let pairs = [
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+            ((50.0, 0.0, 0.0), (50.0, -1.0, 2.0), 2.3669),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            (
+                (60.2574, -34.0099, 36.2677),
+                (60.4626, -34.1751, 39.4387),
+                1.2644,
+            ),
+        ];
+        for (lab1, lab2, expected) in pairs {
+            let d = ciede2000(lab1, lab2);
+            assert!((d - expected).abs() < 1e-4, "{} != {}", d, expected);
+        }
+        let white = RGB::new(255, 255, 255);
+        assert!(color_distance(ColorMetric::Ciede2000, white, white) < 1e-9);
+        assert!(color_distance(ColorMetric::Ciede2000, white, RGB::new(252, 252, 252)) < 2.0);
+    }
+
+    #[test]
+    fn suggest_from_samples() {
+        let samples = [
+            RGB::new(200, 50, 50),
+            RGB::new(204, 52, 50),
+            RGB::new(196, 48, 50),
+            RGB::new(200, 50, 50),
+        ];
+        let s = suggest_tolerance(ColorMetric::ChannelMax, &samples).unwrap();
+        assert_eq!(s.rgb, RGB::new(200, 50, 50));
+        // 最大偏差 4, 平均值 2 加 3 倍标准差 2
+        assert_eq!(s.tolerance, 8.0);
+        assert_eq!(s.saturation, None);
+        for sample in samples {
+            assert!(color_matches(
+                ColorMetric::ChannelMax,
+                sample,
+                s.rgb,
+                s.tolerance,
+                None
+            ));
+        }
+
+        let hsv = suggest_tolerance(ColorMetric::Hsv, &samples).unwrap();
+        assert!(hsv.saturation.is_some());
+        let steady = suggest_tolerance(ColorMetric::Euclidean, &[RGB::new(1, 2, 3); 5]).unwrap();
+        assert_eq!(steady.tolerance, 0.0);
+        assert!(suggest_tolerance(ColorMetric::Ciede2000, &[]).is_none());
+    }
+
+    #[test]
+    fn color_node_defaults() {
+        let json = r#"{"coordinate": "1,2", "rgb": "3,4,5"}"#;
+        match serde_json::from_str(json).unwrap() {
+            ElementEnum::Color(co) => {
+                assert_eq!(co.metric, ColorMetric::ChannelMax);
+                assert_eq!(co.tolerance, 0.0);
+            }
+            other => panic!("not a color: {:?}", other),
+        }
+        let json =
+            r#"{"coordinate": "1,2", "rgb": "3,4,5", "metric": "ciede2000", "tolerance": 2.5}"#;
+        match serde_json::from_str(json).unwrap() {
+            ElementEnum::Color(co) => {
+                assert_eq!(co.metric, ColorMetric::Ciede2000);
+                assert_eq!(co.tolerance, 2.5);
+            }
+            other => panic!("not a color: {:?}", other),
+        }
+    }
+}
@@ -1 +1,24 @@
-pub mod test;
\ No newline at end of file
+pub mod attach;
+pub mod bar;
+pub mod capture;
+pub mod conflict;
+pub mod coord;
+pub mod debug;
+pub mod digits;
+#[cfg(test)]
+pub mod fixture;
+pub mod hook;
+pub mod hotkey;
+pub mod metric;
+pub mod monitor;
+pub mod pattern;
+pub mod picker;
+pub mod pointer;
+pub mod probe;
+pub mod process;
+pub mod press;
+pub mod schedule;
+pub mod screen;
+pub mod template;
+pub mod value;
+pub mod watch;
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use image::{Rgb, RgbImage};
+
+    use crate::{
+        global::{common::RGB, model::CoordinateSpace},
+        rgb::{
+            capture::FrameCache,
+            coord::transform_for,
+            file::ImageSource,
+            monitor::{
+                find_monitor, monitor_at, relative_to_primary, sort_monitors, virtual_bounds,
+                Monitor,
+            },
+            source::ScreenSource,
+        },
+    };
+
+    fn monitor(name: &str, x: i32, y: i32, width: u32, height: u32, primary: bool) -> Monitor {
+        Monitor {
+            index: 0,
+            name: name.to_string(),
+            x,
+            y,
+            width,
+            height,
+            primary,
+        }
+    }
+
+    // 主显示器 1920x1080, 左边一个 1280x1024, 上边一个 1920x1080
+    fn layout() -> Vec<Monitor> {
+        sort_monitors(vec![
+            monitor("\\\\.\\DISPLAY1", 0, 0, 1920, 1080, true),
+            monitor("\\\\.\\DISPLAY3", 0, -1080, 1920, 1080, false),
+            monitor("\\\\.\\DISPLAY2", -1280, 0, 1280, 1024, false),
+        ])
+    }
+
+    #[test]
+    fn enumerate_layout() {
+        let monitors = layout();
+        let names: Vec<&str> = monitors.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["\\\\.\\DISPLAY2", "\\\\.\\DISPLAY3", "\\\\.\\DISPLAY1"]
+        );
+        assert_eq!(monitors[0].index, 1);
+
+        assert_eq!(find_monitor(&monitors, "primary").unwrap().index, 3);
+        assert_eq!(find_monitor(&monitors, "1").unwrap().x, -1280);
+        assert_eq!(find_monitor(&monitors, "\\\\.\\DISPLAY3").unwrap().y, -1080);
+        assert!(find_monitor(&monitors, "4").is_none());
+
+        assert_eq!(monitor_at(&monitors, -1, 500).unwrap().index, 1);
+        assert_eq!(monitor_at(&monitors, 100, -1).unwrap().index, 2);
+        // 左边显示器较矮, 下方没有画面
+        assert!(monitor_at(&monitors, -1, 1050).is_none());
+        assert_eq!(virtual_bounds(&monitors), Some((-1280, -1080, 3200, 2160)));
+    }
+
+    #[test]
+    fn x11_root_origin() {
+        // X11 以根窗口左上角为原点, 主显示器在右边
+        let mut monitors = vec![
+            monitor("HDMI-1", 0, 0, 1280, 1024, false),
+            monitor("DP-1", 1280, 0, 1920, 1080, true),
+        ];
+        assert_eq!(relative_to_primary(&mut monitors), (1280, 0));
+        assert_eq!(monitors[0].rect(), (-1280, 0, 1280, 1024));
+        assert_eq!(monitors[1].rect(), (0, 0, 1920, 1080));
+
+        let mut none = vec![monitor("HDMI-1", 10, 10, 100, 100, false)];
+        assert_eq!(relative_to_primary(&mut none), (0, 0));
+        assert_eq!(none[0].rect(), (10, 10, 100, 100));
+    }
+
+    #[test]
+    fn monitor_coordinates() {
+        let monitors = layout();
+        let space = CoordinateSpace::Monitor {
+            monitor: "1".to_string(),
+            width: None,
+            height: None,
+        };
+        let t = transform_for(&space, (1920, 1080), &monitors, |_| {
+            Err("no window".to_string())
+        })
+        .unwrap();
+        assert_eq!(t.point(10, 20), (-1270, 20));
+
+        // 按 2560x2048 记录的坐标换算到左边显示器
+        let space = CoordinateSpace::Monitor {
+            monitor: "1".to_string(),
+            width: Some(2560),
+            height: Some(2048),
+        };
+        let t = transform_for(&space, (1920, 1080), &monitors, |_| {
+            Err("no window".to_string())
+        })
+        .unwrap();
+        assert_eq!(t.point(2560, 2048), (0, 1024));
+
+        let missing = CoordinateSpace::Monitor {
+            monitor: "HDMI-1".to_string(),
+            width: None,
+            height: None,
+        };
+        assert!(transform_for(&missing, (1920, 1080), &monitors, |_| Err(String::new())).is_err());
+    }
+
+    #[test]
+    fn negative_coordinates() {
+        // 虚拟桌面截图, 左上角在 (-20, -10), 主显示器从 (0, 0) 开始为蓝色
+        let image = RgbImage::from_fn(40, 20, |x, y| {
+            if x >= 20 && y >= 10 {
+                Rgb([0, 0, 255])
+            } else {
+                Rgb([255, 0, 0])
+            }
+        });
+        let source = ImageSource::from_image(image).with_origin(-20, -10);
+
+        assert_eq!(source.pixel(-20, -10).unwrap(), RGB::new(255, 0, 0));
+        assert_eq!(source.pixel(-1, -1).unwrap(), RGB::new(255, 0, 0));
+        assert_eq!(source.pixel(0, 0).unwrap(), RGB::new(0, 0, 255));
+        assert!(source.pixel(-21, 0).is_err());
+        assert!(source.pixel(20, 0).is_err());
+        assert_eq!(source.monitors().unwrap()[0].rect(), (-20, -10, 40, 20));
+
+        // 跨显示器的截图区域
+        let frame = source.region(-2, -2, 4, 4).unwrap();
+        assert_eq!(frame.pixel(-2, -2), Some(RGB::new(255, 0, 0)));
+        assert_eq!(frame.pixel(1, 1), Some(RGB::new(0, 0, 255)));
+        let mut cache = FrameCache::new(&[(-5, -5), (5, 5)], None);
+        let now = std::time::Instant::now();
+        assert_eq!(
+            cache.pixel(&source, -5, -5, now).unwrap(),
+            RGB::new(255, 0, 0)
+        );
+        assert_eq!(
+            cache.pixel(&source, 5, 5, now).unwrap(),
+            RGB::new(0, 0, 255)
+        );
+    }
+}
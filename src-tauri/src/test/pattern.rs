@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        global::{
+            common::RGB,
+            model::{ColorPattern, ElementEnum},
+        },
+        rgb::pattern::{parse_region, Pattern},
+    };
+
+    fn parse(json: &str) -> ColorPattern {
+        match serde_json::from_str(json).unwrap() {
+            ElementEnum::ColorPattern(cp) => cp,
+            other => panic!("not a color pattern: {:?}", other),
+        }
+    }
+
+    // 红色背景, (20,10) 开始有一个 白/黑/白 的横条
+    fn screen(x: i32, y: i32) -> Option<RGB> {
+        if !(0..50).contains(&x) || !(0..30).contains(&y) {
+            return None;
+        }
+        match (x - 20, y - 10) {
+            (0, 0) | (2, 0) => Some(RGB::new(255, 255, 255)),
+            (1, 0) => Some(RGB::new(0, 0, 0)),
+            _ => Some(RGB::new(255, 0, 0)),
+        }
+    }
+
+    const BAR: &str = r#"{"points": [
+        {"dx": 0, "dy": 0, "rgb": "255,255,255"},
+        {"dx": 1, "dy": 0, "rgb": "0,0,0"},
+        {"dx": 2, "dy": 0, "rgb": "250,250,250", "tolerance": 5}
+    ], "anchor": "20,10"}"#;
+
+    #[test]
+    fn all_points_must_match() {
+        let cp = parse(BAR);
+        let pattern = Pattern::parse(&cp).unwrap();
+        assert_eq!(pattern.min_match, 3);
+        assert!(pattern.matches_at(20, 10, screen));
+        // 锚点颜色相同但其他点不同
+        assert!(!pattern.matches_at(22, 10, screen));
+        assert!(!pattern.matches_at(5, 5, screen));
+    }
+
+    #[test]
+    fn k_of_n() {
+        let mut cp = parse(BAR);
+        cp.points[1].rgb = "0,255,0".to_string();
+        assert!(!Pattern::parse(&cp).unwrap().matches_at(20, 10, screen));
+        cp.min_match = Some(2);
+        let pattern = Pattern::parse(&cp).unwrap();
+        assert!(pattern.matches_at(20, 10, screen));
+        assert!(!pattern.matches_at(21, 10, screen));
+        // min_match 超过点数时按全部匹配处理
+        cp.min_match = Some(10);
+        assert_eq!(Pattern::parse(&cp).unwrap().min_match, 3);
+    }
+
+    #[test]
+    fn search_region() {
+        let mut cp = parse(BAR);
+        cp.anchor = None;
+        cp.search = Some("0,0,48,30".to_string());
+        let pattern = Pattern::parse(&cp).unwrap();
+        let region = parse_region(cp.search.as_deref().unwrap()).unwrap();
+        assert_eq!(pattern.find(region, screen), Some((20, 10)));
+        assert_eq!(pattern.find((0, 0, 10, 10), screen), None);
+        // 截图区域需要包含搜索区域加上取色点的偏移
+        assert_eq!(
+            pattern.corners(&cp),
+            vec![(0, 0), (2, 0), (47, 29), (49, 29)]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Pattern::parse(&parse(r#"{"points": []}"#)).is_err());
+        let mut cp = parse(BAR);
+        cp.points[0].rgb = "red".to_string();
+        assert!(Pattern::parse(&cp).is_err());
+        assert_eq!(parse_region("1,2,3,4"), Some((1, 2, 3, 4)));
+        assert_eq!(parse_region("1,2,0,4"), None);
+        assert_eq!(parse_region("1,2,3"), None);
+    }
+}
@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        sync::mpsc::{channel, RecvTimeoutError},
+        time::{Duration, Instant},
+    };
+
+    use crate::{
+        input::hook::InputEvent,
+        rgb::pick::{pick_at, run_picker, PickOptions, PickOutcome},
+        test::fixture::red_blue,
+    };
+
+    const F1: u32 = 112;
+    const F2: u32 = 113;
+    const CTRL: u32 = 17;
+    const ESC: u32 = 27;
+
+    fn press(code: u32) -> InputEvent {
+        InputEvent {
+            code,
+            pressed: true,
+            time: Instant::now(),
+        }
+    }
+
+    // 依次返回事件, 没有事件后等待超时
+    fn events(
+        list: Vec<InputEvent>,
+    ) -> impl FnMut(Duration) -> Result<InputEvent, RecvTimeoutError> {
+        let mut list = list.into_iter();
+        move |timeout| {
+            list.next().ok_or_else(|| {
+                std::thread::sleep(timeout);
+                RecvTimeoutError::Timeout
+            })
+        }
+    }
+
+    #[test]
+    fn pick_with_hotkey() {
+        let source = red_blue();
+        let updates = RefCell::new(Vec::new());
+        let outcome = run_picker(
+            &PickOptions::default(),
+            &source,
+            events(vec![press(65), press(F1)]),
+            || Some((10, 5)),
+            |_| false,
+            |u| updates.borrow_mut().push(u.clone()),
+        )
+        .unwrap();
+        let result = match outcome {
+            PickOutcome::Picked(result) => result,
+            other => panic!("not picked: {:?}", other),
+        };
+        assert_eq!(result.coordinate, "10,5");
+        assert_eq!(result.rgb, "0,0,200");
+        assert_eq!(result.hex, "#0000c8");
+        // 5×5 邻域跨过红蓝分界, 2 列红色 3 列蓝色
+        assert_eq!(result.size, 5);
+        assert_eq!(result.mean, [80.0, 0.0, 120.0]);
+        assert_eq!(result.variance[1], 0.0);
+        assert!((result.variance[0] - 9600.0).abs() < 1e-6);
+
+        let updates = updates.borrow();
+        assert!(!updates.is_empty());
+        assert_eq!(updates[0].hex, "#0000c8");
+    }
+
+    #[test]
+    fn escape_and_timeout() {
+        let source = red_blue();
+        let outcome = run_picker(
+            &PickOptions::default(),
+            &source,
+            events(vec![press(ESC)]),
+            || Some((1, 1)),
+            |_| false,
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(outcome, PickOutcome::Cancelled);
+
+        // 默认也可以按 Ctrl 取色
+        let outcome = run_picker(
+            &PickOptions::default(),
+            &source,
+            events(vec![press(CTRL)]),
+            || Some((1, 1)),
+            |code| code == CTRL,
+            |_| {},
+        )
+        .unwrap();
+        assert!(matches!(outcome, PickOutcome::Picked(_)));
+
+        let options = PickOptions {
+            timeout: Some(30),
+            interval: Some(10),
+            ..Default::default()
+        };
+        let started = Instant::now();
+        let outcome = run_picker(
+            &options,
+            &source,
+            events(vec![]),
+            || None,
+            |_| false,
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(outcome, PickOutcome::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        // 输入监听断开时结束
+        let (tx, rx) = channel::<InputEvent>();
+        drop(tx);
+        assert!(run_picker(
+            &PickOptions::default(),
+            &source,
+            |t| rx.recv_timeout(t),
+            || None,
+            |_| false,
+            |_| {}
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn custom_hotkey() {
+        let source = red_blue();
+        let options = PickOptions {
+            hotkey: Some("Ctrl+F2".to_string()),
+            size: Some(3),
+            ..Default::default()
+        };
+        // 没有按住 Ctrl 的 F2 和默认的 F1 都不会取色
+        let outcome = run_picker(
+            &options,
+            &source,
+            events(vec![press(F1), press(F2), press(ESC)]),
+            || Some((2, 2)),
+            |_| false,
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(outcome, PickOutcome::Cancelled);
+
+        let outcome = run_picker(
+            &options,
+            &source,
+            events(vec![press(CTRL), press(F2)]),
+            || Some((2, 2)),
+            |code| code == CTRL,
+            |_| {},
+        )
+        .unwrap();
+        match outcome {
+            PickOutcome::Picked(result) => {
+                assert_eq!(result.size, 3);
+                assert_eq!(result.mean, [200.0, 0.0, 0.0]);
+                assert_eq!(result.variance, [0.0, 0.0, 0.0]);
+            }
+            other => panic!("not picked: {:?}", other),
+        }
+        let invalid = PickOptions {
+            hotkey: Some("Ctrl+Nope".to_string()),
+            ..Default::default()
+        };
+        assert!(run_picker(
+            &invalid,
+            &source,
+            events(vec![]),
+            || None,
+            |_| false,
+            |_| {}
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn edge_neighbourhood() {
+        let source = red_blue();
+        // 邻域超出截图时只统计该像素
+        let result = pick_at(&source, 0, 0, 5).unwrap();
+        assert_eq!(result.size, 1);
+        assert_eq!(result.hex, "#c80000");
+        // 偶数大小按奇数处理, 保证鼠标位置在中心
+        assert_eq!(pick_at(&source, 5, 5, 4).unwrap().size, 5);
+        assert!(pick_at(&source, 30, 0, 1).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::memory::{
+        pointer::{parse_name, walk, Offset, PointerTable},
+        value::{PointerWidth, ValueType},
+    };
+
+    // 模拟进程内存, 地址 -> 4 字节指针
+    fn read_from(
+        memory: &HashMap<usize, usize>,
+    ) -> impl FnMut(usize) -> Result<usize, String> + '_ {
+        move |address| {
+            memory
+                .get(&address)
+                .copied()
+                .ok_or_else(|| format!("读取失败: {:#X}", address))
+        }
+    }
+
+    #[test]
+    fn default_chains() {
+        let table = PointerTable::default();
+        assert_eq!(table.pointer_size, PointerWidth::Bits32);
+        // 和原来写死的偏移一致
+        let (def, offsets) = table.lookup("mana").unwrap();
+        assert_eq!(offsets, vec![0x00C910E4, 0x2C, 0xC, 0xA0]);
+        assert_eq!(def.value_type, ValueType::U32);
+        assert!(def.module.is_none());
+        let (_, offsets) = table.lookup("lrf[16]").unwrap();
+        assert_eq!(offsets, vec![0x00C912F0, 0x25C, 16]);
+        let (_, offsets) = table.lookup("skill_plan[0x14]").unwrap();
+        assert_eq!(
+            offsets,
+            vec![0x00C910E4, 0x2C, 0x0C, 0x00058B48, 0x00017A80, 0x28, 0x14, 0x10]
+        );
+        let (_, offsets) = table.lookup("skill").unwrap();
+        assert_eq!(offsets, vec![0x00C912F0, 0x60]);
+        assert_eq!(def.size(), 4);
+    }
+
+    #[test]
+    fn lookup_errors() {
+        let table = PointerTable::default();
+        assert!(table.lookup("hp").is_err());
+        // 参数数量不对
+        assert!(table.lookup("skill_plan").is_err());
+        assert!(table.lookup("mana[1]").is_err());
+        assert!(table.lookup("skill_plan[x]").is_err());
+        assert!(table.lookup("skill_plan[1").is_err());
+
+        assert_eq!(parse_name(" hp ").unwrap(), ("hp", vec![]));
+        assert_eq!(parse_name("buff[2, 0x10]").unwrap(), ("buff", vec![2, 16]));
+    }
+
+    #[test]
+    fn parse_table() {
+        let table = PointerTable::parse(
+            r#"{"values": {
+                "hp": {"module": "game.dll", "offsets": [1024, "0x10"], "type": "f32"},
+                "buff": {"offsets": ["0x20", "index", "slot"], "type": "i16", "params": ["slot", "index"]}
+            }}"#,
+        )
+        .unwrap();
+        assert_eq!(table.pointer_size, PointerWidth::Bits32);
+        let hp = &table.values["hp"];
+        assert_eq!(hp.module.as_deref(), Some("game.dll"));
+        assert_eq!(hp.offsets, vec![Offset::Value(1024), Offset::Value(16)]);
+        assert_eq!(hp.value_type, ValueType::F32);
+        // 参数按 params 的顺序传入
+        let (_, offsets) = table.lookup("buff[3, 8]").unwrap();
+        assert_eq!(offsets, vec![0x20, 8, 3]);
+
+        // 未声明的参数、空偏移和不支持的指针大小
+        assert!(PointerTable::parse(r#"{"values": {"a": {"offsets": ["slot"]}}}"#).is_err());
+        assert!(PointerTable::parse(r#"{"values": {"a": {"offsets": []}}}"#).is_err());
+        assert!(
+            PointerTable::parse(r#"{"pointer_size": 2, "values": {"a": {"offsets": [1]}}}"#)
+                .is_err()
+        );
+        assert!(
+            PointerTable::parse(r#"{"values": {"a": {"offsets": [1], "type": "u128"}}}"#).is_err()
+        );
+        // 字符串需要 length, 64 位指针
+        assert!(
+            PointerTable::parse(r#"{"values": {"a": {"offsets": [1], "type": "gbk"}}}"#).is_err()
+        );
+        let table = PointerTable::parse(
+            r#"{"pointer_size": 8, "values": {"name": {"offsets": [1], "type": "utf16", "length": 16}}}"#,
+        )
+        .unwrap();
+        assert_eq!(table.pointer_size, PointerWidth::Bits64);
+        assert_eq!(table.values["name"].size(), 32);
+    }
+
+    #[test]
+    fn walk_chain() {
+        // base + 0x10 -> 0x5000, 0x5000 + 0x4 -> 0x6000, 值在 0x6000 + 0x8
+        let memory = HashMap::from([(0x1010, 0x5000), (0x5004, 0x6000)]);
+        let address = walk::<String>(0x1000, &[0x10, 0x4, 0x8], read_from(&memory)).unwrap();
+        assert_eq!(address, 0x6008);
+        // 只有一个偏移时直接是基址 + 偏移
+        assert_eq!(
+            walk::<String>(0x1000, &[0x20], read_from(&memory)),
+            Ok(0x1020)
+        );
+        // 读取失败和空指针
+        assert!(walk::<String>(0x1000, &[0x30, 0x4], read_from(&memory)).is_err());
+        let memory = HashMap::from([(0x1010, 0)]);
+        let e = walk::<String>(0x1000, &[0x10, 0x4], read_from(&memory)).unwrap_err();
+        assert!(e.contains("0x1010"));
+    }
+}
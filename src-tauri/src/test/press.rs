@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::act::press::{AutoOff, DoubleTap, Press, TapHold};
+
+    #[test]
+    fn short_press_is_tap() {
+        let start = Instant::now();
+        let mut press = TapHold::new(Duration::from_millis(100));
+        press.press(start);
+        assert_eq!(press.poll(start + Duration::from_millis(50)), None);
+        assert_eq!(
+            press.release(start + Duration::from_millis(80)),
+            Some(Press::Tap)
+        );
+        assert_eq!(press.deadline(), None);
+    }
+
+    #[test]
+    fn long_press_starts_and_ends_hold() {
+        let start = Instant::now();
+        let mut press = TapHold::new(Duration::from_millis(100));
+        press.press(start);
+        assert_eq!(press.deadline(), Some(start + Duration::from_millis(100)));
+        assert_eq!(
+            press.poll(start + Duration::from_millis(100)),
+            Some(Press::HoldStart)
+        );
+        // 长按只触发一次
+        assert_eq!(press.poll(start + Duration::from_millis(300)), None);
+        assert_eq!(
+            press.release(start + Duration::from_millis(500)),
+            Some(Press::HoldEnd)
+        );
+    }
+
+    #[test]
+    fn late_release_is_hold() {
+        let start = Instant::now();
+        let mut press = TapHold::new(Duration::from_millis(100));
+        press.press(start);
+        // 还没有调用 poll 就在长按时间之后抬起
+        assert_eq!(
+            press.release(start + Duration::from_millis(120)),
+            Some(Press::LateHold)
+        );
+        assert_eq!(press.deadline(), None);
+        assert_eq!(press.poll(start + Duration::from_millis(200)), None);
+    }
+
+    #[test]
+    fn repeated_press_keeps_first_time() {
+        let start = Instant::now();
+        let mut press = TapHold::new(Duration::from_millis(100));
+        press.press(start);
+        press.press(start + Duration::from_millis(90));
+        assert_eq!(
+            press.poll(start + Duration::from_millis(100)),
+            Some(Press::HoldStart)
+        );
+    }
+
+    #[test]
+    fn release_without_press_is_ignored() {
+        let mut press = TapHold::new(Duration::from_millis(100));
+        assert_eq!(press.release(Instant::now()), None);
+    }
+
+    #[test]
+    fn double_tap_within_window() {
+        let start = Instant::now();
+        let mut double = DoubleTap::new(Duration::from_millis(300));
+        assert!(!double.press(start));
+        assert!(double.press(start + Duration::from_millis(200)));
+        // 第三次按下重新计数
+        assert!(!double.press(start + Duration::from_millis(300)));
+        // 超过间隔不算双击
+        assert!(!double.press(start + Duration::from_millis(700)));
+        assert!(double.press(start + Duration::from_millis(800)));
+    }
+
+    #[test]
+    fn auto_off_after_limit() {
+        let start = Instant::now();
+        let mut auto_off = AutoOff::new(Some(Duration::from_secs(5)));
+        assert_eq!(auto_off.deadline(), None);
+        auto_off.start(start);
+        assert_eq!(auto_off.deadline(), Some(start + Duration::from_secs(5)));
+        assert!(!auto_off.poll(start + Duration::from_secs(4)));
+        assert!(auto_off.poll(start + Duration::from_secs(5)));
+        assert!(!auto_off.poll(start + Duration::from_secs(6)));
+
+        // 手动停止后不再自动停止
+        auto_off.start(start);
+        auto_off.stop();
+        assert!(!auto_off.poll(start + Duration::from_secs(10)));
+
+        let mut never = AutoOff::new(None);
+        never.start(start);
+        assert_eq!(never.deadline(), None);
+    }
+}
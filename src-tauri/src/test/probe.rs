@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            mpsc::{channel, RecvTimeoutError},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use crate::{
+        global::model::{CaptureMode, Color, ColorMetric},
+        rgb::probe::{probe_frames, read_pixels, unwatch_pixels, watch_pixels},
+        test::fixture::red_blue,
+    };
+
+    fn probe(coordinate: &str, rgb: &str, tolerance: f64) -> Color {
+        Color {
+            coordinate: coordinate.to_string(),
+            rgb: rgb.to_string(),
+            tolerance,
+            metric: ColorMetric::ChannelMax,
+            saturation: None,
+            capture: CaptureMode::Off,
+            node: None,
+        }
+    }
+
+    #[test]
+    fn readings() {
+        let source = red_blue();
+        let probes = vec![
+            probe("1,1", "205,0,0", 5.0),
+            probe("15,1", "205,0,0", 5.0),
+            probe("15,2", "", 0.0),
+            probe("30,1", "0,0,0", 0.0),
+            probe("abc", "0,0,0", 0.0),
+        ];
+        // 和订阅一样截取所有坐标所在的区域, 超出画面的坐标不影响截图
+        let mut frames = probe_frames(&probes);
+        let pixels = read_pixels(&source, &probes, &mut frames);
+
+        assert_eq!(pixels[0].hex.as_deref(), Some("#c80000"));
+        assert_eq!(pixels[0].matched, Some(true));
+        assert_eq!(pixels[0].distance, Some(5.0));
+        assert_eq!(pixels[1].rgb.as_deref(), Some("0,0,200"));
+        assert_eq!(pixels[1].matched, Some(false));
+        // 没有填写颜色时只显示当前颜色
+        assert_eq!(pixels[2].rgb.as_deref(), Some("0,0,200"));
+        assert_eq!(pixels[2].matched, None);
+        // 超出画面和坐标有误时返回错误, 不影响其他坐标
+        assert!(pixels[3].error.is_some() && pixels[3].rgb.is_none());
+        assert!(pixels[4].error.is_some());
+    }
+
+    #[test]
+    fn stream_until_unsubscribed() {
+        let (tx, rx) = channel();
+        let id = watch_pixels(
+            Arc::new(red_blue()),
+            vec![probe("1,1", "200,0,0", 0.0)],
+            20,
+            move |frame| tx.send(frame).is_ok(),
+        );
+        for _ in 0..3 {
+            let frame = rx.recv_timeout(Duration::from_secs(1)).unwrap();
+            assert_eq!(frame.id, id);
+            assert_eq!(frame.pixels[0].matched, Some(true));
+        }
+        assert!(unwatch_pixels(id));
+        assert!(!unwatch_pixels(id));
+        // 停止后回调被释放, 通道断开
+        loop {
+            match rx.recv_timeout(Duration::from_secs(1)) {
+                Ok(_) => continue,
+                Err(e) => {
+                    assert_eq!(e, RecvTimeoutError::Disconnected);
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn stream_with_offscreen_probe() {
+        let (tx, rx) = channel();
+        let id = watch_pixels(
+            Arc::new(red_blue()),
+            vec![probe("1,1", "200,0,0", 0.0), probe("30,1", "0,0,0", 0.0)],
+            20,
+            move |frame| tx.send(frame).is_ok(),
+        );
+        let frame = rx.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(unwatch_pixels(id));
+        assert_eq!(frame.pixels[0].matched, Some(true));
+        assert!(frame.pixels[1].error.is_some());
+    }
+
+    #[test]
+    fn stop_when_receiver_gone() {
+        let (tx, rx) = channel();
+        let (done_tx, done_rx) = channel::<()>();
+        let id = watch_pixels(
+            Arc::new(red_blue()),
+            vec![probe("1,1", "", 0.0)],
+            20,
+            move |frame| {
+                let _keep = &done_tx;
+                tx.send(frame).is_ok()
+            },
+        );
+        rx.recv_timeout(Duration::from_secs(1)).unwrap();
+        // 界面不再接收时自动停止
+        drop(rx);
+        assert_eq!(
+            done_rx.recv_timeout(Duration::from_secs(1)),
+            Err(RecvTimeoutError::Disconnected)
+        );
+        assert!(!unwatch_pixels(id));
+    }
+}
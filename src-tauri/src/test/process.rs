@@ -0,0 +1,296 @@
+#[cfg(test)]
+mod tests {
+    use crate::memory::{
+        pointer::PointerTable,
+        process::{
+            find_processes, select_process, MemoryError, ProcessInfo, ProcessMemory, TargetProcess,
+        },
+        value::{MemoryValue, PointerWidth},
+    };
+
+    // 模拟进程内存, 从 base 开始的一段字节
+    struct FakeProcess {
+        base: usize,
+        bytes: Vec<u8>,
+    }
+
+    impl ProcessMemory for FakeProcess {
+        fn read_bytes(&self, address: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
+            let start = address
+                .checked_sub(self.base)
+                .filter(|start| *start < self.bytes.len())
+                .ok_or_else(|| MemoryError::ReadMemoryFailed(format!("{:#X}", address)))?;
+            let end = (start + size).min(self.bytes.len());
+            if end - start != size {
+                return Err(MemoryError::ShortRead {
+                    address,
+                    expected: size,
+                    read: end - start,
+                });
+            }
+            Ok(self.bytes[start..end].to_vec())
+        }
+
+        fn module_base(&self, module: Option<&str>) -> Result<usize, MemoryError> {
+            match module {
+                None => Ok(self.base),
+                Some(_) => Ok(self.base + 0x40),
+            }
+        }
+
+        fn pid(&self) -> u32 {
+            1
+        }
+
+        fn is_alive(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn default_methods() {
+        // 0x1000 -> 0x1020, 0x1020 + 0x8 处为 -3, 模块基址 0x1040 处为 0x1050
+        let mut bytes = vec![0u8; 0x60];
+        bytes[..4].copy_from_slice(&0x1020u32.to_le_bytes());
+        bytes[0x28..0x2C].copy_from_slice(&(-3i32).to_le_bytes());
+        bytes[0x40..0x44].copy_from_slice(&0x1050u32.to_le_bytes());
+        bytes[0x54..0x58].copy_from_slice(b"ok\0\0");
+        let process = FakeProcess {
+            base: 0x1000,
+            bytes,
+        };
+        assert_eq!(process.read::<i32>(0x1028).unwrap(), -3);
+        assert_eq!(
+            process.read_pointer(0x1000, PointerWidth::Bits32).unwrap(),
+            0x1020
+        );
+        assert!(matches!(
+            process.read::<u64>(0x105C),
+            Err(MemoryError::ShortRead {
+                expected: 8,
+                read: 4,
+                ..
+            })
+        ));
+
+        let table = PointerTable::parse(
+            r#"{"values": {
+                "hp": {"offsets": [0, 8], "type": "i32"},
+                "name": {"module": "game.dll", "offsets": [0, 4], "type": "utf8", "length": 4}
+            }}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            process.read_value_in(&table, "hp").unwrap(),
+            MemoryValue::Int(-3)
+        );
+        assert_eq!(
+            process.read_value_in(&table, "name").unwrap(),
+            MemoryValue::Text("ok".to_string())
+        );
+        assert!(process.read_value_in(&table, "mp").is_err());
+    }
+
+    #[test]
+    fn select_target() {
+        let processes = || {
+            vec![
+                ProcessInfo {
+                    pid: 300,
+                    name: "Client.exe".to_string(),
+                },
+                ProcessInfo {
+                    pid: 20,
+                    name: "explorer.exe".to_string(),
+                },
+                ProcessInfo {
+                    pid: 120,
+                    name: "client.EXE".to_string(),
+                },
+            ]
+        };
+        let found = find_processes(processes(), " client.exe ");
+        assert_eq!(
+            found.iter().map(|p| p.pid).collect::<Vec<_>>(),
+            vec![120, 300]
+        );
+        assert_eq!(find_processes(processes(), "").len(), 3);
+
+        // 没有选择时使用进程ID最小的进程
+        let mut target = TargetProcess::new("Client.exe");
+        assert_eq!(select_process(processes(), &target).unwrap(), 120);
+        target.pid = Some(300);
+        assert_eq!(select_process(processes(), &target).unwrap(), 300);
+        // 进程ID不存在或进程名不一致
+        target.pid = Some(20);
+        assert!(select_process(processes(), &target).is_err());
+        target.pid = Some(301);
+        assert!(select_process(processes(), &target).is_err());
+        assert!(select_process(processes(), &TargetProcess::new("Game.exe")).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn parse_maps() {
+        use crate::memory::linux::{module_base_in_maps, process_name};
+
+        let maps = "\
+55d0c8a00000-55d0c8a02000 r--p 00000000 08:01 1234                       /usr/bin/game
+55d0c8a02000-55d0c8a05000 r-xp 00002000 08:01 1234                       /usr/bin/game
+7f0000000000-7f0000001000 rw-p 00000000 00:00 0
+7f1000000000-7f1000200000 r-xp 00000000 08:01 99   /home/a/My Games/drive_c/Game/Client.exe
+7ffd00000000-7ffd00021000 rw-p 00000000 00:00 0                          [stack]
+";
+        assert_eq!(module_base_in_maps(maps, "game"), Some(0x55d0c8a00000));
+        assert_eq!(
+            module_base_in_maps(maps, "client.EXE"),
+            Some(0x7f1000000000)
+        );
+        assert_eq!(module_base_in_maps(maps, "[stack]"), Some(0x7ffd00000000));
+        assert_eq!(module_base_in_maps(maps, "libc.so.6"), None);
+
+        assert_eq!(
+            process_name(Some("/usr/bin/game"), b"game\0-x\0").as_deref(),
+            Some("game")
+        );
+        // wine 中运行的 exe
+        assert_eq!(
+            process_name(
+                Some("/usr/bin/wine64-preloader"),
+                b"C:\\Game\\Client.EXE\0-window\0"
+            )
+            .as_deref(),
+            Some("Client.EXE")
+        );
+        assert_eq!(process_name(None, b""), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn process_chain() {
+        use std::{
+            io::{BufRead, BufReader},
+            process::{Child, Command, Stdio},
+        };
+
+        use crate::memory::{
+            linux::{module_base_in_maps, LinuxProcess},
+            process::{list_processes, open_process},
+        };
+
+        // 测试失败时也结束被读取的进程
+        struct Fixture(Child);
+        impl Drop for Fixture {
+            fn drop(&mut self) {
+                let _ = self.0.kill();
+                let _ = self.0.wait();
+            }
+        }
+
+        // 被读取的进程: examples/memory_fixture.rs, cargo test 时和测试一起编译
+        let exe = std::env::current_exe().unwrap();
+        let path = exe
+            .parent()
+            .and_then(|deps| deps.parent())
+            .unwrap()
+            .join("examples")
+            .join("memory_fixture");
+        // 只运行部分测试时 cargo 不会编译 examples
+        if !path.exists() {
+            let status = Command::new(std::env::var("CARGO").unwrap_or("cargo".to_string()))
+                .args(["build", "--example", "memory_fixture", "--manifest-path"])
+                .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"))
+                .status()
+                .unwrap();
+            assert!(status.success(), "编译 memory_fixture 失败");
+        }
+        let mut fixture = Fixture(
+            Command::new(&path)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()
+                .unwrap(),
+        );
+        let line = BufReader::new(fixture.0.stdout.take().unwrap())
+            .lines()
+            .map_while(Result::ok)
+            .find_map(|line| Some(line.strip_prefix("fixture: ")?.to_string()))
+            .expect("fixture 没有输出地址");
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let root: usize = fields[0].parse().unwrap();
+        let edge: usize = fields[1].parse().unwrap();
+        let module = "memory_fixture";
+        let maps = std::fs::read_to_string(format!("/proc/{}/maps", fixture.0.id())).unwrap();
+        let offset = root - module_base_in_maps(&maps, module).unwrap();
+
+        let table = PointerTable::parse(&format!(
+            r#"{{"pointer_size": {size}, "values": {{
+                "mana": {{"offsets": [{root}, "0x10", "0x10"], "type": "u32"}},
+                "module_mana": {{"module": "{module}", "offsets": [{offset}, "0x10", "0x10"], "type": "u32"}},
+                "speed": {{"offsets": [{root}, "0x10", "0x14"], "type": "f32"}},
+                "name": {{"offsets": [{root}, "0x10", "0x20"], "type": "gbk", "length": 16}},
+                "title": {{"offsets": [{root}, "0x10", "0x30"], "type": "utf16", "length": 8}},
+                "slot": {{"offsets": [{root}, "slot", "0x10"], "type": "u32", "params": ["slot"]}}
+            }}}}"#,
+            size = std::mem::size_of::<usize>(),
+        ))
+        .unwrap();
+
+        let process = LinuxProcess::attach(fixture.0.id(), 0);
+        assert!(process.is_alive());
+        assert_eq!(
+            process.read_value_in(&table, "mana").unwrap(),
+            MemoryValue::Int(1234)
+        );
+        assert_eq!(
+            process.read_value_in(&table, "module_mana").unwrap(),
+            MemoryValue::Int(1234)
+        );
+        assert_eq!(
+            process.read_value_in(&table, "speed").unwrap(),
+            MemoryValue::Float(1.5)
+        );
+        assert_eq!(
+            process.read_value_in(&table, "name").unwrap(),
+            MemoryValue::Text("火球".to_string())
+        );
+        assert_eq!(
+            process.read_value_in(&table, "title").unwrap(),
+            MemoryValue::Text("冰箭".to_string())
+        );
+        assert_eq!(
+            process.read_value_in(&table, "slot[16]").unwrap(),
+            MemoryValue::Int(1234)
+        );
+        // 按进程名和进程ID选择同名进程中的 fixture
+        let found = find_processes(list_processes().unwrap(), module);
+        assert!(found.iter().any(|p| p.pid == fixture.0.id()));
+        let target = TargetProcess {
+            name: module.to_string(),
+            pid: Some(fixture.0.id()),
+        };
+        let opened = open_process(&target, 0).unwrap();
+        assert_eq!(opened.pid(), fixture.0.id());
+        assert_eq!(
+            opened.read_value_in(&table, "mana").unwrap(),
+            MemoryValue::Int(1234)
+        );
+        // holder[0] 为空指针
+        let e = process.read_value_in(&table, "slot[0]").unwrap_err();
+        assert!(matches!(e, MemoryError::Pointer(ref e) if e.contains("空指针")));
+        // 跨过不可读的页时不返回部分数据
+        assert_eq!(process.read::<u32>(edge).unwrap(), 0);
+        assert!(matches!(
+            process.read::<u64>(edge),
+            Err(MemoryError::ShortRead {
+                expected: 8,
+                read: 4,
+                ..
+            })
+        ));
+
+        drop(fixture);
+        assert!(!process.is_alive());
+        assert!(process.read::<u32>(edge).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use crate::{
+        act::{
+            schedule::{Clock, Schedule},
+            trigger::run_timer,
+        },
+        global::model::Timer,
+    };
+
+    // 虚拟时钟, sleep 只推进时间
+    struct VirtualClock {
+        now: Cell<Instant>,
+    }
+
+    impl VirtualClock {
+        fn new() -> Self {
+            VirtualClock {
+                now: Cell::new(Instant::now()),
+            }
+        }
+    }
+
+    impl Clock for VirtualClock {
+        fn now(&self) -> Instant {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+        }
+    }
+
+    // 执行线程: busy 为 true 时一直执行到被停止
+    fn worker(stop_flag: Arc<AtomicBool>, busy: bool) -> thread::JoinHandle<()> {
+        let handle = thread::spawn(move || {
+            while busy && !stop_flag.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(1));
+            }
+        });
+        // 等待线程结束, 避免下一次检查时线程还没来得及结束
+        while !busy && !handle.is_finished() {
+            thread::yield_now();
+        }
+        handle
+    }
+
+    // 使用虚拟时钟运行 run_timer 直到 total, 返回每次执行时相对开始的时间ms
+    fn run(timer: Timer, total: u64, paused: impl Fn(u64) -> bool, busy: bool) -> Vec<u64> {
+        let clock = VirtualClock::new();
+        let start = clock.now();
+        let elapsed = || (clock.now() - start).as_millis() as u64;
+        let runs = RefCell::new(Vec::new());
+        run_timer(
+            0,
+            &timer,
+            &clock,
+            || elapsed() > total,
+            || paused(elapsed()),
+            |stop_flag| {
+                runs.borrow_mut().push(elapsed());
+                worker(stop_flag, busy)
+            },
+        );
+        runs.into_inner()
+    }
+
+    #[test]
+    fn interval_fires_repeatedly() {
+        let timer = Timer {
+            interval: Some(100),
+            delay: None,
+        };
+        assert_eq!(run(timer, 350, |_| false, false), vec![100, 200, 300]);
+    }
+
+    #[test]
+    fn delay_then_interval() {
+        let timer = Timer {
+            interval: Some(100),
+            delay: Some(30),
+        };
+        assert_eq!(run(timer, 250, |_| false, false), vec![30, 130, 230]);
+    }
+
+    #[test]
+    fn delay_only_fires_once() {
+        let timer = Timer {
+            interval: None,
+            delay: Some(50),
+        };
+        assert_eq!(run(timer, 500, |_| false, false), vec![50]);
+    }
+
+    #[test]
+    fn paused_ticks_are_skipped() {
+        let timer = Timer {
+            interval: Some(100),
+            delay: None,
+        };
+        let runs = run(timer, 450, |elapsed| (150..=250).contains(&elapsed), false);
+        assert_eq!(runs, vec![100, 300, 400]);
+    }
+
+    #[test]
+    fn busy_worker_skips_ticks() {
+        let timer = Timer {
+            interval: Some(100),
+            delay: None,
+        };
+        // 上一次执行没有结束时跳过
+        assert_eq!(run(timer.clone(), 450, |_| false, true), vec![100]);
+        // 暂停时停止执行线程, 恢复后的下一次正常执行
+        let runs = run(timer, 450, |elapsed| elapsed == 200, true);
+        assert_eq!(runs, vec![100, 300]);
+    }
+
+    #[test]
+    fn missed_ticks_do_not_burst() {
+        let timer = Timer {
+            interval: Some(100),
+            delay: None,
+        };
+        let clock = VirtualClock::new();
+        let start = clock.now();
+        let mut schedule = Schedule::new(&timer, start);
+        clock.sleep(Duration::from_millis(350));
+        assert!(schedule.due(clock.now()));
+        assert!(!schedule.due(clock.now()));
+        assert_eq!(
+            schedule.next_run(),
+            Some(start + Duration::from_millis(400))
+        );
+    }
+
+    #[test]
+    fn empty_timer_never_fires() {
+        assert!(run(Timer::default(), 500, |_| false, false).is_empty());
+    }
+}
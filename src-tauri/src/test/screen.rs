@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+    use image::{Rgb, RgbImage};
+
+    use crate::{
+        global::common::RGB,
+        rgb::{check::check_color_on, file::ImageSource, source::ScreenSource},
+    };
+
+    // 左半边红色, 右半边蓝色, (5,5) 为白色
+    fn screenshot() -> RgbImage {
+        let mut image = RgbImage::from_fn(20, 10, |x, _| {
+            if x < 10 {
+                Rgb([255, 0, 0])
+            } else {
+                Rgb([0, 0, 255])
+            }
+        });
+        image.put_pixel(5, 5, Rgb([255, 255, 255]));
+        image
+    }
+
+    #[test]
+    fn read_saved_screenshot() {
+        // 保存为 png 后再读取, 和使用游戏截图时一样
+        let path = std::env::temp_dir().join(format!("ruby_read_{}.png", std::process::id()));
+        screenshot().save(&path).unwrap();
+        let source = ImageSource::open(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(source.size().unwrap(), (20, 10));
+        assert_eq!(source.pixel(5, 5).unwrap(), RGB::new(255, 255, 255));
+        assert_eq!(source.pixel(15, 0).unwrap(), RGB::new(0, 0, 255));
+
+        let frame = source.region(4, 4, 3, 3).unwrap();
+        assert_eq!(frame.pixels.len(), 9);
+        assert_eq!(frame.pixel(5, 5), Some(RGB::new(255, 255, 255)));
+        assert_eq!(frame.pixel(4, 4), Some(RGB::new(255, 0, 0)));
+        assert_eq!(frame.pixel(7, 4), None);
+
+        assert!(source.region(18, 0, 5, 1).is_err());
+        assert!(source.pixel(-1, 0).is_err());
+        assert!(ImageSource::open(std::env::temp_dir().join("ruby_screen_missing.png")).is_err());
+    }
+
+    #[test]
+    fn color_condition_on_screenshot() {
+        let source = ImageSource::from_image(screenshot());
+        assert!(check_color_on(&source, "255,0,0".into(), "1,1".into(), 0));
+        assert!(!check_color_on(&source, "255,0,0".into(), "12,1".into(), 0));
+        assert!(check_color_on(
+            &source,
+            "250,250,250".into(),
+            "5,5".into(),
+            5
+        ));
+        // 超出截图范围时不成立
+        assert!(!check_color_on(&source, "255,0,0".into(), "30,1".into(), 0));
+    }
+
+    // 需要 X11 显示, 例如 `xvfb-run cargo test`, 没有 DISPLAY 时跳过
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn x11_region() {
+        use crate::rgb::x11::X11Source;
+
+        if std::env::var("DISPLAY").is_err() {
+            return;
+        }
+        let source = X11Source::connect().unwrap();
+        let (width, height) = source.size().unwrap();
+        assert!(width > 0 && height > 0);
+        let frame = source.region(0, 0, 4, 2).unwrap();
+        assert_eq!(frame.pixels.len(), 8);
+        // 主显示器左上角为 (0, 0)
+        let monitors = source.monitors().unwrap();
+        let primary = monitors.iter().find(|m| m.primary).unwrap();
+        assert_eq!(primary.rect(), (0, 0, width, height));
+    }
+}
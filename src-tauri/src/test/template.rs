@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use image::{imageops, Rgb, RgbImage, Rgba, RgbaImage};
+
+    use crate::rgb::{
+        file::ImageSource,
+        source::ScreenSource,
+        template::{template_path, TemplateImage},
+    };
+
+    // 带纹理的截图, 避免模板在多个位置都能匹配
+    fn screenshot() -> RgbImage {
+        RgbImage::from_fn(64, 48, |x, y| {
+            Rgb([
+                (x * 7 + y * 3) as u8,
+                ((x * 13) ^ (y * 5)) as u8,
+                (y * 11 + x) as u8,
+            ])
+        })
+    }
+
+    fn fixture() -> ImageSource {
+        ImageSource::from_image(screenshot())
+    }
+
+    fn open_template(image: &RgbaImage) -> TemplateImage {
+        TemplateImage::from_image(image).unwrap()
+    }
+
+    fn crop(x: u32, y: u32, width: u32, height: u32) -> RgbaImage {
+        let rgba = imageops::crop_imm(&screenshot(), x, y, width, height).to_image();
+        RgbaImage::from_fn(width, height, |px, py| {
+            let [r, g, b] = rgba.get_pixel(px, py).0;
+            Rgba([r, g, b, 255])
+        })
+    }
+
+    #[test]
+    fn find_icon_in_region() {
+        let source = fixture();
+        let frame = source.region(0, 0, 64, 48).unwrap();
+        let template = open_template(&crop(30, 20, 8, 6));
+        assert_eq!((template.width, template.height), (8, 6));
+
+        let found = template.find(&frame, (10, 10, 40, 30), 0.9).unwrap();
+        assert_eq!((found.x, found.y), (30, 20));
+        assert_eq!(found.similarity, 1.0);
+
+        // 模板不完整落在搜索区域内时找不到
+        assert!(template.find(&frame, (0, 0, 35, 48), 0.9).is_none());
+        assert!(template.find(&frame, (0, 0, 4, 4), 0.9).is_none());
+    }
+
+    #[test]
+    fn similarity_threshold() {
+        let source = fixture();
+        let frame = source.region(0, 0, 64, 48).unwrap();
+        let mut icon = crop(5, 5, 6, 6);
+        // 部分像素有偏差, 例如 buff 图标上的倒计时
+        for x in 0..6 {
+            let p = icon.get_pixel_mut(x, 0);
+            p.0[0] = p.0[0].wrapping_add(40);
+        }
+        let template = open_template(&icon);
+        let found = template.find(&frame, (0, 0, 64, 48), 0.9).unwrap();
+        assert_eq!((found.x, found.y), (5, 5));
+        assert!(found.similarity < 1.0 && found.similarity > 0.95);
+        assert!(template.find(&frame, (0, 0, 64, 48), 0.999).is_none());
+    }
+
+    #[test]
+    fn transparent_pixels_ignored() {
+        let source = fixture();
+        let frame = source.region(0, 0, 64, 48).unwrap();
+        let mut icon = crop(40, 30, 6, 6);
+        // 圆形图标四角透明, 颜色随意
+        for (x, y) in [(0, 0), (5, 0), (0, 5), (5, 5)] {
+            icon.put_pixel(x, y, Rgba([0, 0, 0, 0]));
+        }
+        let template = open_template(&icon);
+        let found = template.find(&frame, (0, 0, 64, 48), 1.0).unwrap();
+        assert_eq!((found.x, found.y), (40, 30));
+        // 完全透明的模板没有可比较的像素
+        assert!(TemplateImage::from_image(&RgbaImage::new(4, 4)).is_none());
+    }
+
+    #[test]
+    fn template_names() {
+        assert!(template_path("../config").is_err());
+        assert!(template_path("a/b").is_err());
+        assert!(template_path("").is_err());
+        if let Ok(path) = template_path("buff") {
+            assert!(path.ends_with("templates/buff.png"));
+        }
+    }
+}
@@ -1,11 +0,0 @@
-#[cfg(test)]
-mod tests {
-
-    use crate::rgb::pick::mouse_rgb;
-    #[test]
-    fn test() {
-        let a = mouse_rgb();
-        println!("Mouse RGB: {}", a);
-    }
-
-}
\ No newline at end of file
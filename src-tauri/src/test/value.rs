@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use crate::memory::value::{from_bytes, MemoryValue, PointerWidth, ValueType};
+
+    #[test]
+    fn decode_numbers() {
+        let bytes = [0xFE, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0];
+        assert_eq!(ValueType::U8.decode(&bytes[..1]), Ok(MemoryValue::Int(254)));
+        assert_eq!(ValueType::I8.decode(&bytes[..1]), Ok(MemoryValue::Int(-2)));
+        assert_eq!(
+            ValueType::U16.decode(&bytes[..2]),
+            Ok(MemoryValue::Int(0xFFFE))
+        );
+        assert_eq!(ValueType::I16.decode(&bytes[..2]), Ok(MemoryValue::Int(-2)));
+        assert_eq!(
+            ValueType::U32.decode(&bytes[..4]),
+            Ok(MemoryValue::Int(0xFFFFFFFE))
+        );
+        assert_eq!(ValueType::I32.decode(&bytes[..4]), Ok(MemoryValue::Int(-2)));
+        assert_eq!(
+            ValueType::U64.decode(&bytes),
+            Ok(MemoryValue::UInt(0xFFFFFFFE))
+        );
+        assert_eq!(
+            ValueType::I64.decode(&(-5i64).to_le_bytes()),
+            Ok(MemoryValue::Int(-5))
+        );
+        assert_eq!(
+            ValueType::F32.decode(&1.5f32.to_le_bytes()),
+            Ok(MemoryValue::Float(1.5))
+        );
+        let value = ValueType::F64.decode(&(-2.25f64).to_le_bytes()).unwrap();
+        assert_eq!(value.as_i64(), Some(-2));
+        assert_eq!(value.as_f64(), Some(-2.25));
+        assert_eq!(ValueType::Bool.decode(&[2]), Ok(MemoryValue::Bool(true)));
+        assert_eq!(MemoryValue::Bool(false).as_i64(), Some(0));
+        // 超出 i64 的 u64 不能转换
+        let max = ValueType::U64.decode(&u64::MAX.to_le_bytes()).unwrap();
+        assert_eq!(max.as_i64(), None);
+        assert_eq!(max.as_f64(), Some(u64::MAX as f64));
+    }
+
+    #[test]
+    fn wrong_length() {
+        // 字节不够或多余时返回错误, 不解析部分数据
+        assert!(ValueType::U32.decode(&[1, 2, 3]).is_err());
+        assert!(ValueType::I16.decode(&[1, 2, 3]).is_err());
+        assert!(ValueType::F64.decode(&[0; 4]).is_err());
+        assert!(ValueType::Bool.decode(&[]).is_err());
+        assert!(ValueType::Utf16.decode(&[0x41, 0, 0x42]).is_err());
+        assert_eq!(from_bytes::<i32>(&[1, 0, 0, 0]), Ok(1));
+        assert!(from_bytes::<u64>(&[1, 0, 0, 0]).is_err());
+        assert!(from_bytes::<u8>(&[]).is_err());
+    }
+
+    #[test]
+    fn decode_text() {
+        assert_eq!(
+            ValueType::Bytes.decode(&[1, 0, 2]),
+            Ok(MemoryValue::Bytes(vec![1, 0, 2]))
+        );
+        assert_eq!(
+            ValueType::Utf8.decode(b"mana\0xx"),
+            Ok(MemoryValue::Text("mana".to_string()))
+        );
+        assert_eq!(
+            ValueType::Utf8.decode("火球".as_bytes()),
+            Ok(MemoryValue::Text("火球".to_string()))
+        );
+        // "火球" 的 GBK 编码
+        assert_eq!(
+            ValueType::Gbk.decode(&[0xBB, 0xF0, 0xC7, 0xF2, 0, 0xFF]),
+            Ok(MemoryValue::Text("火球".to_string()))
+        );
+        let utf16: Vec<u8> = "冰箭\0x"
+            .encode_utf16()
+            .flat_map(|c| c.to_le_bytes())
+            .collect();
+        assert_eq!(
+            ValueType::Utf16.decode(&utf16),
+            Ok(MemoryValue::Text("冰箭".to_string()))
+        );
+        assert_eq!(MemoryValue::Text("1".to_string()).as_i64(), None);
+        assert_eq!(MemoryValue::Bytes(vec![1]).as_f64(), None);
+    }
+
+    #[test]
+    fn sizes() {
+        assert_eq!(ValueType::U8.size(0), 1);
+        assert_eq!(ValueType::I16.size(10), 2);
+        assert_eq!(ValueType::F32.size(0), 4);
+        assert_eq!(ValueType::I64.size(0), 8);
+        assert_eq!(ValueType::Bytes.size(6), 6);
+        assert_eq!(ValueType::Gbk.size(6), 6);
+        assert_eq!(ValueType::Utf16.size(6), 12);
+        assert!(ValueType::Utf8.has_length());
+        assert!(!ValueType::Bool.has_length());
+    }
+
+    #[test]
+    fn pointer_width() {
+        let bytes = [0x78, 0x56, 0x34, 0x12, 0, 0, 0, 0];
+        assert_eq!(PointerWidth::Bits32.decode(&bytes[..4]), Ok(0x12345678));
+        assert_eq!(PointerWidth::Bits64.decode(&bytes), Ok(0x12345678));
+        // 宽度和字节数不一致
+        assert!(PointerWidth::Bits32.decode(&bytes).is_err());
+        assert!(PointerWidth::Bits64.decode(&bytes[..4]).is_err());
+
+        assert_eq!(PointerWidth::default().size(), 4);
+        assert_eq!(
+            serde_json::from_str::<PointerWidth>("8").unwrap(),
+            PointerWidth::Bits64
+        );
+        assert!(serde_json::from_str::<PointerWidth>("2").is_err());
+        assert_eq!(serde_json::to_string(&PointerWidth::Bits32).unwrap(), "4");
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        act::watch::{mana_active, ConditionState},
+        global::model::{Watch, WatchCondition},
+    };
+
+    #[test]
+    fn state_switches_after_confirm() {
+        let mut state = ConditionState::new(3);
+        assert_eq!(state.update(true), None);
+        assert_eq!(state.update(true), None);
+        assert_eq!(state.update(true), Some(true));
+        assert!(state.is_active());
+        // 抖动时不切换
+        assert_eq!(state.update(false), None);
+        assert_eq!(state.update(true), None);
+        assert_eq!(state.update(false), None);
+        assert_eq!(state.update(false), None);
+        assert_eq!(state.update(false), Some(false));
+    }
+
+    #[test]
+    fn mana_hysteresis() {
+        // 低于100成立, 高于150才结束
+        assert!(!mana_active(120, 100, Some(150), false));
+        assert!(mana_active(100, 100, Some(150), false));
+        assert!(mana_active(140, 100, Some(150), true));
+        assert!(!mana_active(151, 100, Some(150), true));
+        // 未设置 release 时没有滞回
+        assert!(!mana_active(101, 100, None, true));
+    }
+
+    #[test]
+    fn parse_watch() {
+        let watch: Watch = serde_json::from_str(
+            r#"{"condition": {"t": "pixel", "coordinate": "100,200", "rgb": "255,0,0"}, "edge": true}"#,
+        )
+        .unwrap();
+        assert!(watch.edge);
+        assert!(matches!(
+            watch.condition,
+            WatchCondition::Pixel {
+                deviation: 0,
+                mismatch: false,
+                ..
+            }
+        ));
+
+        let watch: Watch = serde_json::from_str(
+            r#"{"condition": {"t": "mana", "below": 300, "release": 500}, "confirm": 2}"#,
+        )
+        .unwrap();
+        assert!(!watch.edge);
+        assert_eq!(watch.confirm, Some(2));
+    }
+}
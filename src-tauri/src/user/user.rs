@@ -4,6 +4,8 @@ use serde_json::{json, Value};
 use std::fs;
 use std::path::PathBuf;
 
+use crate::{global::global::PROCESS_NAME, rgb::debug::CaptureConfig, start::hotkey::HotKeyConfig};
+
 pub fn local_mac_address() -> String {
     let mut mac_md5: String = "".to_string();
 
@@ -98,7 +100,8 @@ pub fn create_user_config_dir() {
             // 如果配置文件不存在，创建默认配置
             if !config_file.exists() {
                 let default_config = json!({
-                    "hold_on_time": 100
+                    "hold_on_time": 100,
+                    "hotkeys": HotKeyConfig::default()
                 });
 
                 // 将JSON写入文件
@@ -174,6 +177,26 @@ pub fn get_config_path() -> Option<PathBuf> {
     dirs::home_dir().map(|home| home.join(".ruby").join("config.json"))
 }
 
+// 模板图片目录
+pub fn get_templates_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ruby").join("templates"))
+}
+
+// 调试截图目录
+pub fn get_captures_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ruby").join("captures"))
+}
+
+// 内存指针定义文件
+pub fn get_pointers_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ruby").join("pointers.json"))
+}
+
+// 数字字形目录, 每个字形集一个子目录
+pub fn get_digits_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ruby").join("digits"))
+}
+
 // 获取配置文件内容的hod_on_time的值
 #[allow(dead_code)]
 pub fn get_hod_on_time() -> std::io::Result<i64> {
@@ -188,3 +211,32 @@ pub fn get_hod_on_time() -> std::io::Result<i64> {
         "hod_on_time not found in config",
     ))
 }
+
+// 获取配置文件中的全局快捷键, 未配置时使用默认值
+pub fn get_hotkeys() -> std::io::Result<HotKeyConfig> {
+    let config = read_user_config()?;
+    match config.get("hotkeys") {
+        Some(value) => serde_json::from_value(value.clone())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+        None => Ok(HotKeyConfig::default()),
+    }
+}
+
+// 获取配置文件中的目标进程名, 未配置时使用默认进程名
+pub fn get_process_name() -> String {
+    read_user_config()
+        .ok()
+        .and_then(|config| config.get("process_name")?.as_str().map(str::to_string))
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| PROCESS_NAME.clone())
+}
+
+// 获取配置文件中的调试截图限制, 未配置时使用默认值
+pub fn get_capture_config() -> std::io::Result<CaptureConfig> {
+    let config = read_user_config()?;
+    match config.get("captures") {
+        Some(value) => serde_json::from_value(value.clone())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+        None => Ok(CaptureConfig::default()),
+    }
+}
@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { TimerData } from "./types";
 
 interface ContextMenuHandlers {
@@ -73,23 +74,75 @@ export const createContextMenuItems = (
     colorBtn.className = "x6-context-menu-item";
     colorBtn.innerText = "取色";
     colorBtn.onclick = async () => {
+      menu.remove();
+      const oldFill = node.attr("body/fill");
+      // 取色过程中节点颜色跟随鼠标位置
+      const unlisten = await listen<{ hex: string }>("color_picker", (e) => {
+        node.attr("body/fill", e.payload.hex);
+      });
+      messageApi.info("按 F1 或左 Ctrl 取色, Esc 取消");
       try {
-        const now_mouse_rgb: string = await invoke("get_mouse_rgb");
-        const [coordinate, rgb] = now_mouse_rgb.split("|");
-        const rgbArray = rgb.split(",").map(Number);
-        const hex = `#${rgbArray
-          .map((x) => x.toString(16).padStart(2, "0"))
+        const result: { coordinate: string; rgb: string; hex: string } =
+          await invoke("pick_color");
+        node.attr("body/fill", result.hex);
+        node.setData({ coordinate: result.coordinate, rgb: result.rgb });
+        messageApi.success("取色成功");
+      } catch (err) {
+        node.attr("body/fill", oldFill);
+        messageApi.error(`取色失败: ${err}`);
+      } finally {
+        unlisten();
+      }
+    };
+    menu.appendChild(colorBtn);
+
+    const sampleBtn = document.createElement("div");
+    sampleBtn.className = "x6-context-menu-item";
+    sampleBtn.innerText = "采样取色";
+    sampleBtn.onclick = async () => {
+      menu.remove();
+      const oldFill = node.attr("body/fill");
+      // 取色和采样过程中节点颜色跟随读取到的颜色
+      const unlisten = await listen<{ hex: string }>("color_picker", (e) => {
+        node.attr("body/fill", e.payload.hex);
+      });
+      // 和取色共用同一个取色过程, 按 Esc 或点击提示都可以取消
+      messageApi.open({
+        key: "sample",
+        type: "loading",
+        content: "按 F1 或左 Ctrl 开始采样, Esc 或点击此处取消",
+        duration: 0,
+        onClick: () => invoke("cancel_color_picker"),
+      });
+      try {
+        // 多次取色, 根据颜色波动设置偏差
+        const sample: {
+          coordinate: string;
+          rgb: string;
+          tolerance: number;
+          metric: string;
+          saturation?: number;
+        } = await invoke("get_mouse_rgb_samples", {
+          metric: nodeData?.metric || "channel_max",
+        });
+        const hex = `#${sample.rgb
+          .split(",")
+          .map((x) => Number(x).toString(16).padStart(2, "0"))
           .join("")}`;
 
         node.attr("body/fill", hex);
-        node.setData({ coordinate, rgb });
-        messageApi.success("取色成功");
+        // 只更新取色相关的字段, 保留节点上的其他设置
+        node.setData({ ...node.getData(), ...sample });
+        messageApi.success(`取色成功, 偏差 ${sample.tolerance}`);
       } catch (err) {
-        messageApi.error("取色失败");
+        node.attr("body/fill", oldFill);
+        messageApi.error(`取色失败: ${err}`);
+      } finally {
+        messageApi.destroy("sample");
+        unlisten();
       }
-      menu.remove();
     };
-    menu.appendChild(colorBtn);
+    menu.appendChild(sampleBtn);
 
     const saveBtn = document.createElement("div");
     saveBtn.className = "x6-context-menu-item";
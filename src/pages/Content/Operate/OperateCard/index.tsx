@@ -54,6 +54,11 @@ interface TimeOrNama {
 interface Color {
     coordinate: string;
     rgb: string;
+    tolerance?: number; // 允许的偏差
+    metric?: "channel_max" | "euclidean" | "hsv" | "ciede2000"; // 颜色差异计算方式
+    saturation?: number; // hsv 方式的饱和度偏差
+    capture?: "off" | "always" | "flip"; // 调试截图
+    node?: string; // 节点id, 区分不同节点的调试截图
 }
 
 // 修改 ElementEnum 类型定义
@@ -218,7 +223,12 @@ const OperateCard = forwardRef(({ operate, onEdit }: Props, ref) => {
             } else if (label === "取色" || data.coordinate) {
                 return {
                     coordinate: data.coordinate || "",
-                    rgb: data.rgb || ""
+                    rgb: data.rgb || "",
+                    tolerance: data.tolerance || 0,
+                    metric: data.metric,
+                    saturation: data.saturation,
+                    capture: data.capture,
+                    node: node.id
                 } as Color;
             }
 
@@ -347,7 +357,13 @@ const OperateCard = forwardRef(({ operate, onEdit }: Props, ref) => {
             if (elementsData) {
                 console.log("启用操作 - 转换数据:", elementsData);
                 // 这里可以调用API保存转换后的数据
-                await invoke('run', { elements: [elementsData], t: event })
+                const res = JSON.parse(await invoke<string>('run', { elements: [elementsData], t: event }));
+                if (res.code === 409) {
+                    // 触发键冲突, 规则没有开启
+                    setOpen(false);
+                    messageApi.error(`${res.message}: ${res.data.map((c: any) => `${c.first} 与 ${c.second} ${c.reason}`).join('；')}`);
+                    return;
+                }
                 // elementsData转换成
                 window.sessionStorage.setItem("operate_status", "running");
             }
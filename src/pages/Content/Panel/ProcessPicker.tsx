@@ -0,0 +1,106 @@
+import { useEffect, useState } from "react";
+import { Select, Space, Tag, Typography, message } from "antd";
+import { listen } from "@tauri-apps/api/event";
+import { invoke } from "@tauri-apps/api/core";
+
+type AttachState =
+    | { state: "detached" }
+    | { state: "attached"; pid: number }
+    | { state: "not_found" }
+    | { state: "failed"; error: string };
+
+type TargetProcess = { name: string; pid: number | null };
+
+type ProcessInfo = { pid: number; name: string };
+
+// 连接状态的显示文字和颜色
+const stateTag = (attach: AttachState) => {
+    switch (attach.state) {
+        case "attached":
+            return <Tag color="success">已连接 {attach.pid}</Tag>;
+        case "not_found":
+            return <Tag color="warning">进程没有运行</Tag>;
+        case "failed":
+            return <Tag color="error" title={attach.error}>连接失败</Tag>;
+        default:
+            return <Tag>未连接</Tag>;
+    }
+};
+
+// 目标进程的连接状态, 同时打开多个客户端时选择进程ID
+const ProcessPicker = () => {
+    const [attach, setAttach] = useState<AttachState>({ state: "detached" });
+    const [target, setTarget] = useState<TargetProcess | null>(null);
+    const [processes, setProcesses] = useState<ProcessInfo[]>([]);
+    const [loading, setLoading] = useState(false);
+    const [messageApi, contextHolder] = message.useMessage();
+
+    const readTarget = async () => {
+        try {
+            setTarget(await invoke<TargetProcess>("read_target_process"));
+        } catch (error) {
+            console.error("读取目标进程失败:", error);
+        }
+    };
+
+    useEffect(() => {
+        invoke<AttachState>("process_attach_state").then(setAttach);
+        readTarget();
+        // 进程退出时选择的进程ID会被自动清除, 状态变化时重新读取目标
+        const unlisten = listen<AttachState>("process_attach", (event) => {
+            setAttach(event.payload);
+            readTarget();
+        });
+        return () => {
+            unlisten.then((f) => f());
+        };
+    }, []);
+
+    const loadProcesses = async (open: boolean) => {
+        if (!open) {
+            return;
+        }
+        setLoading(true);
+        try {
+            setProcesses(await invoke<ProcessInfo[]>("list_target_processes"));
+        } catch (error) {
+            messageApi.error(`${error}`);
+        } finally {
+            setLoading(false);
+        }
+    };
+
+    const selectProcess = async (pid?: number) => {
+        try {
+            setTarget(await invoke<TargetProcess>("select_target_process", { pid: pid ?? null }));
+        } catch (error) {
+            messageApi.error(`选择进程失败: ${error}`);
+            readTarget();
+        }
+    };
+
+    return (
+        <>
+            {contextHolder}
+            <Space direction="vertical" style={{ width: '80%' }}>
+                <Space>
+                    <Typography.Text>{target?.name ?? "目标进程"}</Typography.Text>
+                    {stateTag(attach)}
+                </Space>
+                <Select
+                    allowClear
+                    style={{ width: '100%' }}
+                    placeholder="第一个匹配的进程"
+                    loading={loading}
+                    value={target?.pid ?? undefined}
+                    onOpenChange={loadProcesses}
+                    onChange={(pid) => selectProcess(pid)}
+                    options={processes.map((p) => ({ value: p.pid, label: `${p.name} (${p.pid})` }))}
+                    notFoundContent="没有正在运行的进程"
+                />
+            </Space>
+        </>
+    );
+};
+
+export default ProcessPicker;
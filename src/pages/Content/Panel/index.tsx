@@ -18,6 +18,7 @@ import { getVersion } from '@tauri-apps/api/app';
 import "./Panel.css";
 import BlurCircle from "./BlurCircle";
 import ActionDrawer from "./ActionDrawer";
+import ProcessPicker from "./ProcessPicker";
 import { invoke } from '@tauri-apps/api/core';
 
 const Panel = () => {
@@ -123,6 +124,11 @@ const Panel = () => {
                         </div>
 
                     </Col>
+                    <Col span={24}>
+                        <div className="circle-container">
+                            <ProcessPicker />
+                        </div>
+                    </Col>
                     {/* <Col span={24}>
                         <div className="circle-container">
                             <Space align="center" style={{ width: '80%', justifyContent: 'space-between' }}>
@@ -202,6 +208,7 @@ const Panel = () => {
                                                 messageApi.success("内存清理成功！");
                                             } catch (error) {
                                                 console.error("清理内存失败:", error);
+                                                messageApi.error(`清理内存失败: ${error}`);
                                             }
                                         }
                                     });